@@ -0,0 +1,98 @@
+use anyhow::Result;
+use colored::*;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Where build progress and command output go
+///
+/// The default log prints progress messages and only shows a command's
+/// output when it fails. A prefixed log streams every line of command output
+/// as it arrives, tagged with the project name, so concurrent builds stay
+/// readable.
+#[derive(Debug, Clone, Default)]
+pub struct BuildLog {
+    prefix: Option<String>,
+    /// Print progress on stderr, keeping stdout for the program being built
+    stderr: bool,
+}
+
+impl BuildLog {
+    pub fn prefixed(name: &str) -> Self {
+        Self {
+            prefix: Some(format!("[{}]", name)),
+            stderr: false,
+        }
+    }
+    
+    /// A log that prints progress on stderr, for builds ahead of `murex run`
+    pub fn stderr() -> Self {
+        Self {
+            prefix: None,
+            stderr: true,
+        }
+    }
+    
+    /// Print a progress message
+    pub fn info(&self, message: &str) {
+        let line = match &self.prefix {
+            Some(prefix) => format!("{} {}", prefix.bright_blue(), message.trim_start()),
+            None => message.to_string(),
+        };
+        
+        if self.stderr {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    }
+    
+    /// Run a build command, failing with `failure` and the command's stderr if it exits unsuccessfully
+    pub fn run(&self, command: &mut Command, failure: &str) -> Result<()> {
+        let prefix = match &self.prefix {
+            Some(prefix) => prefix.clone(),
+            None => {
+                let output = command.output()?;
+                
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(anyhow::anyhow!("{}:\n{}", failure, stderr));
+                }
+                
+                return Ok(());
+            }
+        };
+        
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+            
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        
+        let stdout_prefix = prefix.clone();
+        let stdout_thread = thread::spawn(move || stream_lines(stdout, &stdout_prefix));
+        let stderr_lines = stream_lines(stderr, &prefix);
+        let _ = stdout_thread.join();
+        
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("{}:\n{}", failure, stderr_lines.join("\n")));
+        }
+        
+        Ok(())
+    }
+}
+
+/// Echo each line of a child's output with a prefix, returning the lines seen
+fn stream_lines<R: Read>(reader: R, prefix: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        println!("{} {}", prefix.dimmed(), line);
+        lines.push(line);
+    }
+    
+    lines
+}
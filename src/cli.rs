@@ -1,17 +1,32 @@
-use clap::{Subcommand, Parser};
+use clap::{Subcommand, ValueEnum};
 use clap_complete::{generate, Shell};
 use anyhow::Result;
 use colored::*;
-use dialoguer::{Confirm, Select, Input};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::io;
 
-use crate::config::{Config, get_config_dir};
+use crate::build_log::BuildLog;
+use crate::config::{get_config_dir, Config};
+use crate::languages;
 use crate::project::{Project, ProjectManager};
-use crate::templates::{TemplateManager, TemplateType};
+use crate::templates::{self, RenderedFile, TemplateDetails, TemplateFile, TemplateManager};
 use crate::path_manager::PathManager;
+use crate::prompt::Prompter;
+use crate::sync::{SyncAction, SyncReport};
 use crate::utils;
 
+/// How commands that report data print it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Decorated, colored output for people
+    Table,
+    /// Undecorated text for shell scripts: tab-separated columns or key=value lines
+    Plain,
+    /// Pretty-printed JSON
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize a new CLI utility project
@@ -21,6 +36,41 @@ pub enum Commands {
         /// Template to use (rust, python, node, go)
         #[arg(short, long)]
         template: Option<String>,
+        /// Tag to attach to the project (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Build and install right after scaffolding (overrides the auto_build setting)
+        #[arg(long, overrides_with = "no_build")]
+        build: bool,
+        /// Don't build after scaffolding (overrides the auto_build setting)
+        #[arg(long, overrides_with = "build")]
+        no_build: bool,
+        /// Set a template variable without being asked (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        vars: Vec<(String, String)>,
+        /// Show what would be created without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// With --dry-run, also print the contents of every file
+        #[arg(long, requires = "dry_run")]
+        show_contents: bool,
+        /// Don't run the template's post-create hooks
+        #[arg(long)]
+        no_hooks: bool,
+    },
+    /// Register an existing directory as a CLI utility project
+    Adopt {
+        /// Path to the existing project directory
+        path: PathBuf,
+        /// Name to register the project under (defaults to the directory name)
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Template to use (detected from the directory contents by default)
+        #[arg(short, long)]
+        template: Option<String>,
+        /// Tag to attach to the project (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
     },
     /// List all managed CLI utilities
     List,
@@ -28,6 +78,27 @@ pub enum Commands {
     Build {
         /// Name of the CLI utility to build
         name: Option<String>,
+        /// Rebuild even if sources are unchanged since the last build
+        #[arg(short, long)]
+        force: bool,
+        /// Build every registered CLI utility
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
+        /// With --all, only build projects using this template
+        #[arg(long, requires = "all")]
+        template: Option<String>,
+        /// With --all, only build projects carrying this tag (repeatable)
+        #[arg(long = "tag", requires = "all")]
+        tags: Vec<String>,
+        /// With --all, maximum number of concurrent builds
+        #[arg(short, long, requires = "all")]
+        jobs: Option<usize>,
+        /// Install after building without asking
+        #[arg(long, overrides_with = "no_install", conflicts_with = "all")]
+        install: bool,
+        /// Don't install after building, and don't ask
+        #[arg(long, overrides_with = "install", conflicts_with = "all")]
+        no_install: bool,
     },
     /// Remove a CLI utility project
     Remove {
@@ -38,6 +109,20 @@ pub enum Commands {
     Install {
         /// Name of the CLI utility to install
         name: String,
+        /// Rebuild even if sources are unchanged since the last build
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Run a CLI utility without installing it
+    Run {
+        /// Rebuild even if sources are unchanged since the last build
+        #[arg(short, long)]
+        force: bool,
+        /// Name of the CLI utility to run
+        name: String,
+        /// Arguments passed through to the utility (after `--`)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
     },
     /// Uninstall a CLI utility
     Uninstall {
@@ -69,14 +154,84 @@ pub enum TemplateAction {
     Add {
         /// Name of the template
         name: String,
-        /// Path to template directory
+        /// Path to template directory, or a git repository path or URL
+        source: String,
+        /// Clone the source as a git repository (implied for URLs and --ref)
+        #[arg(long)]
+        git: bool,
+        /// Branch, tag or commit to pin a git template to
+        #[arg(long = "ref", value_name = "REF")]
+        reference: Option<String>,
+        /// Short description shown in listings (overrides the manifest's)
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Add every template found in a directory or .tar.gz archive under one namespace
+    AddPack {
+        /// Pack directory or .tar.gz archive
         path: PathBuf,
+        /// Prefix for the pack's template names (defaults to the pack's file name)
+        #[arg(long)]
+        namespace: Option<String>,
+    },
+    /// Remove every template added with a pack
+    RemovePack {
+        /// Namespace the pack was added under
+        namespace: String,
+    },
+    /// Show a template's source, variables, files and required tools
+    Show {
+        /// Name of the template
+        name: String,
+    },
+    /// Check a template's manifest, variables and files, and optionally that it builds
+    Check {
+        /// Name of the template to check
+        name: String,
+        /// Also build a project rendered from the template
+        #[arg(long)]
+        build: bool,
+    },
+    /// Fetch a git template and check out its pinned ref, or a new one
+    Update {
+        /// Name of the template to update
+        name: String,
+        /// Branch, tag or commit to pin to from now on
+        #[arg(long = "ref", value_name = "REF")]
+        reference: Option<String>,
     },
     /// Remove a template
     Remove {
         /// Name of the template to remove
         name: String,
     },
+    /// Merge changes made to a project's template since it was created into the project
+    Sync {
+        /// Name of the project to update
+        project: String,
+    },
+    /// Turn an existing project into a template
+    Save {
+        /// Name of the project to save
+        project: String,
+        /// Name of the new template (defaults to the project name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Where to store the template (defaults to the murex config directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
+    },
+    /// Copy a built-in template's files out so they can be customized
+    Eject {
+        /// Name of the built-in template
+        name: String,
+        /// Where to write the template (defaults to ./<name>)
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Register the ejected copy as a custom template with this name
+        #[arg(long, value_name = "NAME")]
+        add: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -96,13 +251,213 @@ pub enum ConfigAction {
     Reset,
 }
 
-pub fn init_project(name: String, template: Option<String>) -> Result<()> {
+fn print_sync_report(project: &str, report: &SyncReport) -> Result<()> {
+    if report.from == report.to {
+        println!("{} {} is already up to date with its template", "✅".bright_green(), project.bright_blue());
+        return Ok(());
+    }
+    
+    println!(
+        "{} Synced {} with its template ({} → {})",
+        "🔄".bright_blue(),
+        project.bright_blue(),
+        short_commit(&report.from).dimmed(),
+        short_commit(&report.to).bright_yellow()
+    );
+    
+    if report.changes.is_empty() {
+        println!("  No files changed");
+    }
+    
+    for change in &report.changes {
+        let label = match change.action {
+            SyncAction::Added => "added".bright_green(),
+            SyncAction::Updated => "updated".bright_green(),
+            SyncAction::Merged => "merged".bright_blue(),
+            SyncAction::Conflict => "conflict".bright_red(),
+            SyncAction::Removed => "removed".bright_yellow(),
+            SyncAction::Skipped => "skipped".dimmed(),
+        };
+        let note = change.note
+            .as_ref()
+            .map(|note| format!(" ({})", note))
+            .unwrap_or_default();
+        println!("  {:<8} {}{}", label, change.path.display(), note.dimmed());
+    }
+    
+    let conflicts = report.conflicts();
+    if conflicts > 0 {
+        return Err(anyhow::anyhow!("{} file(s) have conflicts; resolve the conflict markers and rebuild", conflicts));
+    }
+    
+    Ok(())
+}
+
+/// Point out that a newly added template replaces a built-in one
+fn print_shadowed_builtin(name: &str) {
+    if languages::get(name).is_some() {
+        println!("  ℹ️  Replaces the built-in {} template (remove it to restore the built-in)", name);
+    }
+}
+
+fn show_template(details: &TemplateDetails) {
+    let info = &details.info;
+    println!("{} Template: {} ({})", "📦".bright_blue(), info.name.bright_green(), info.scope);
+    if let Some(description) = &info.description {
+        println!("  📝 Description: {}", description);
+    }
+    if let Some(path) = &info.path {
+        println!("  📁 Source: {}", path.display());
+    }
+    if let Some(pack) = &info.pack {
+        println!("  🧳 Pack: {}", pack);
+    }
+    if let Some(origin) = &info.origin {
+        println!(
+            "  🔗 Origin: {} ({} at {})",
+            origin.url,
+            origin.reference.as_deref().unwrap_or("default branch"),
+            short_commit(&origin.commit).bright_yellow()
+        );
+    }
+    if !details.requires.is_empty() {
+        println!("  🧰 Requires: {}", details.requires.join(", "));
+    }
+    let build = &details.build;
+    if let Some(language) = &build.language {
+        println!("  🔨 Builds as: {}", language);
+    }
+    for command in &build.commands {
+        println!("  🔨 Build: {}", command);
+    }
+    if let Some(entrypoint) = &build.entrypoint {
+        println!("  🎯 Entrypoint: {}", entrypoint);
+    }
+    for command in &details.hooks.post_create {
+        println!("  🪝 After create: {}", command);
+    }
+    
+    if !details.variables.is_empty() {
+        println!();
+        println!("  Variables:");
+        for variable in &details.variables {
+            let default = variable.default
+                .as_ref()
+                .map(|default| format!(" = {}", default))
+                .unwrap_or_default();
+            println!(
+                "    - {} ({}{}) {}",
+                variable.name.bright_green(),
+                variable.kind,
+                default.dimmed(),
+                variable.prompt.as_deref().unwrap_or("")
+            );
+        }
+    }
+    
+    println!();
+    println!("  Files:");
+    print_file_tree(&details.files);
+}
+
+/// `template show` as key=value lines; keys that can occur more than once are repeated
+fn show_template_plain(details: &TemplateDetails) {
+    let info = &details.info;
+    let origin = info.origin.as_ref();
+    println!("name={}", info.name);
+    println!("scope={}", info.scope);
+    println!("description={}", info.description.as_deref().unwrap_or(""));
+    println!("path={}", info.path.as_ref().map(|path| path.display().to_string()).unwrap_or_default());
+    println!("pack={}", info.pack.as_deref().unwrap_or(""));
+    println!("origin_url={}", origin.map(|origin| origin.url.as_str()).unwrap_or(""));
+    println!("origin_ref={}", origin.and_then(|origin| origin.reference.as_deref()).unwrap_or(""));
+    println!("origin_commit={}", origin.map(|origin| origin.commit.as_str()).unwrap_or(""));
+    println!("requires={}", details.requires.join(","));
+    println!("language={}", details.build.language.as_deref().unwrap_or(""));
+    println!("entrypoint={}", details.build.entrypoint.as_deref().unwrap_or(""));
+    for command in &details.build.commands {
+        println!("build={}", command);
+    }
+    for command in &details.hooks.post_create {
+        println!("post_create={}", command);
+    }
+    for variable in &details.variables {
+        let default = variable.default.as_ref().map(|default| default.to_string()).unwrap_or_default();
+        println!("variable.{}={}", variable.name, default);
+    }
+    for file in &details.files {
+        println!("file={}", file.path.display());
+    }
+}
+
+/// Print relative paths as an indented tree with file sizes
+fn print_file_tree(files: &[TemplateFile]) {
+    let mut printed_dirs = HashSet::new();
+    
+    for file in files {
+        let components: Vec<_> = file.path.components().collect();
+        let Some((name, dirs)) = components.split_last() else {
+            continue;
+        };
+        
+        let mut dir = PathBuf::new();
+        for (depth, component) in dirs.iter().enumerate() {
+            dir.push(component);
+            if printed_dirs.insert(dir.clone()) {
+                println!("{}📁 {}/", "  ".repeat(depth + 2), component.as_os_str().to_string_lossy());
+            }
+        }
+        
+        println!(
+            "{}📄 {} {}",
+            "  ".repeat(dirs.len() + 2),
+            name.as_os_str().to_string_lossy(),
+            utils::format_file_size(file.size).dimmed()
+        );
+    }
+}
+
+/// Whether a template source can only be a git repository
+fn is_git_url(source: &str) -> bool {
+    source.contains("://") || source.starts_with("git@") || source.ends_with(".git")
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+fn parse_key_value(s: &str) -> std::result::Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", s))
+}
+
+/// Per-invocation choices for `murex init`
+pub struct InitOptions {
+    pub template: Option<String>,
+    pub tags: Vec<String>,
+    /// Build and install after scaffolding; `None` follows the `auto_build` setting
+    pub build: Option<bool>,
+    /// Template variable values given on the command line
+    pub vars: Vec<(String, String)>,
+    /// Preview the project instead of creating it
+    pub dry_run: bool,
+    /// Include file contents in the preview
+    pub show_contents: bool,
+    /// Skip the template's post-create hooks
+    pub no_hooks: bool,
+}
+
+pub fn init_project(name: String, options: InitOptions, prompter: &Prompter) -> Result<()> {
+    utils::validate_project_name(&name)?;
+    
     println!("{} Initializing new CLI utility: {}", "✨".bright_green(), name.bright_blue());
     
+    let config = Config::load()?;
     let template_manager = TemplateManager::new()?;
     let available_templates = template_manager.list_templates()?;
     
-    let template_type = match template {
+    let template_type = match options.template {
         Some(t) => {
             if available_templates.contains(&t) {
                 t
@@ -115,46 +470,186 @@ pub fn init_project(name: String, template: Option<String>) -> Result<()> {
             }
         }
         None => {
+            let default_index = available_templates
+                .iter()
+                .position(|t| t == &config.default_template);
+            
             if available_templates.is_empty() {
                 println!("{} No templates available. Creating basic Rust template.", "⚠️".bright_yellow());
                 "rust".to_string()
+            } else if !prompter.is_interactive() {
+                // Nobody to ask, so go straight to the configured default
+                match default_index {
+                    Some(index) => available_templates[index].clone(),
+                    None => return Err(anyhow::anyhow!(
+                        "The default_template '{}' is not an available template; pass --template or run: murex config set default_template <name>",
+                        config.default_template
+                    )),
+                }
             } else {
-                let selection = Select::new()
-                    .with_prompt("Select a template")
-                    .default(0)
-                    .items(&available_templates)
-                    .interact()?;
+                let selection = prompter.select("Select a template", &available_templates, default_index.unwrap_or(0))?;
                 available_templates[selection].clone()
             }
         }
     };
     
+    let variables = template_manager.resolve_variables(&template_type, &name, &options.vars, prompter)?;
+    
     let mut project_manager = ProjectManager::new()?;
-    let project = project_manager.create_project(name.clone(), template_type)?;
+    
+    let hooks = template_manager.post_create_hooks(&template_type, &variables)?;
+    
+    if options.dry_run {
+        let (project, files) = project_manager.plan_project(name, template_type, options.tags, &variables)?;
+        preview_project(&project, &files, options.show_contents);
+        if !hooks.is_empty() && !options.no_hooks {
+            println!();
+            println!("  Post-create hooks:");
+            for command in &hooks.post_create {
+                println!("    🪝 {}", command);
+            }
+        }
+        return Ok(());
+    }
+    
+    let mut project = project_manager.create_project(name.clone(), template_type, options.tags, &variables)?;
     
     println!("{} Successfully created CLI utility: {}", "✅".bright_green(), name.bright_blue());
     println!("  📁 Location: {}", project.path.display());
     println!("  🔧 Template: {}", project.template);
-    println!("");
+    
+    let mut hook_error = None;
+    if !hooks.is_empty() {
+        println!();
+        if options.no_hooks {
+            println!("{} Skipped {} post-create hook(s)", "⏭️".dimmed(), hooks.post_create.len());
+        } else {
+            println!("{} Running post-create hooks", "🪝".bright_blue());
+            // The project is kept, but the failure still makes init exit non-zero below
+            if let Err(e) = hooks.run_post_create(&project.path) {
+                println!("{} {}; the project was created, finish the remaining steps by hand", "⚠️".bright_yellow(), e);
+                hook_error = Some(e);
+            }
+        }
+    }
+    
+    let mut built = false;
+    if options.build.unwrap_or(config.auto_build) {
+        println!();
+        println!("{} Building CLI utility: {}", "🔨".bright_yellow(), name.bright_blue());
+        let result = project_manager
+            .ensure_built(&mut project, true, &BuildLog::default())
+            .and_then(|_| PathManager::new()?.install_project(&project));
+        
+        // The project exists either way, so a failed build is only a warning
+        match result {
+            Ok(()) => built = true,
+            Err(e) => println!("{} Build failed, fix it and run {}:\n{}", "⚠️".bright_yellow(), format!("murex build {}", name).bright_green(), e),
+        }
+    }
+    
+    println!();
     println!("Next steps:");
     println!("  1. cd {}", project.path.display());
-    println!("  2. murex build {}", name);
+    if built {
+        println!("  2. {}", name);
+    } else {
+        println!("  2. murex build {}", name);
+    }
     
-    // Open project in editor
-    let config = Config::load()?;
-    match utils::open_project_in_editor(&project.path, &config) {
-        Ok(()) => println!("  ✨ Opened project in editor!"),
-        Err(e) => println!("  ⚠️  Could not open editor: {}", e.to_string().dimmed()),
+    // Open project in editor (not when running unattended)
+    if prompter.is_interactive() {
+        match utils::open_project_in_editor(&project.path, &config) {
+            Ok(()) => println!("  ✨ Opened project in editor!"),
+            Err(e) => println!("  ⚠️  Could not open editor: {}", e.to_string().dimmed()),
+        }
+    }
+    
+    match hook_error {
+        Some(e) => Err(anyhow::anyhow!("Project {} was created, but a post-create hook failed: {}", name, e)),
+        None => Ok(()),
+    }
+}
+
+/// Print what `init` would create, for `--dry-run`
+fn preview_project(project: &Project, files: &[RenderedFile], show_contents: bool) {
+    println!("{} Dry run, nothing was written", "🔍".bright_yellow());
+    println!("  📁 Location: {}", project.path.display());
+    println!("  🔧 Template: {}", project.template);
+    println!();
+    
+    let tree: Vec<TemplateFile> = files
+        .iter()
+        .map(|file| TemplateFile {
+            path: file.path.clone(),
+            size: file.contents.len() as u64,
+        })
+        .collect();
+    let total: u64 = tree.iter().map(|file| file.size).sum();
+    
+    println!("  Files:");
+    print_file_tree(&tree);
+    println!();
+    println!("  {} files, {}", tree.len(), utils::format_file_size(total));
+    
+    if show_contents {
+        for file in files {
+            println!();
+            println!("{}", format!("── {} ──", file.path.display()).bright_blue());
+            match templates::as_text(&file.contents) {
+                Some(content) => print!("{}", content),
+                None => println!("{}", format!("(binary, {})", utils::format_file_size(file.contents.len() as u64)).dimmed()),
+            }
+        }
     }
+}
+
+pub fn adopt_project(path: PathBuf, name: Option<String>, template: Option<String>, tags: Vec<String>) -> Result<()> {
+    let mut project_manager = ProjectManager::new()?;
+    let project = project_manager.adopt_project(&path, name, template, tags)?;
+    
+    println!("{} Adopted CLI utility: {}", "✅".bright_green(), project.name.bright_blue());
+    println!("  📁 Location: {}", project.path.display());
+    println!("  🔧 Template: {}", project.template);
+    println!();
+    println!("Next steps:");
+    println!("  murex build {}", project.name);
     
     Ok(())
 }
 
-pub fn list_projects() -> Result<()> {
+pub fn list_projects(format: OutputFormat) -> Result<()> {
     let project_manager = ProjectManager::new()?;
     let path_manager = PathManager::new()?;
     let projects = project_manager.list_projects()?;
     
+    match format {
+        OutputFormat::Json => {
+            let statuses = projects
+                .into_iter()
+                .map(|project| path_manager.project_status(project))
+                .collect::<Result<Vec<_>>>()?;
+            println!("{}", serde_json::to_string_pretty(&statuses)?);
+            return Ok(());
+        }
+        OutputFormat::Plain => {
+            for project in projects {
+                let status = path_manager.project_status(project)?;
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    status.project.name,
+                    status.project.template,
+                    status.project.path.display(),
+                    if status.directory_present { "ready" } else { "missing" },
+                    if status.installed { "installed" } else { "not-installed" },
+                    status.project.last_built.as_deref().unwrap_or("-"),
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+    
     if projects.is_empty() {
         println!("{} No CLI utilities found.", "📋".bright_blue());
         println!("Use {} to create your first one!", "murex init <name>".bright_green());
@@ -162,61 +657,65 @@ pub fn list_projects() -> Result<()> {
     }
     
     println!("{} Your CLI utilities:", "📋".bright_blue());
-    println!("");
+    println!();
     
     for project in projects {
-        let status = if project.path.exists() {
+        let status = path_manager.project_status(project)?;
+        let project = &status.project;
+        
+        let ready = if status.directory_present {
             "✅ Ready".bright_green()
         } else {
             "❌ Missing".bright_red()
         };
         
-        let installed = if path_manager.find_project_binary(&project).is_ok() {
+        let installed = if status.installed {
             "✅ Installed".bright_green()
         } else {
             "❌ Not installed".bright_red()
         };
         
-        println!("  {} {}", project.name.bright_blue(), status);
+        println!("  {} {}", project.name.bright_blue(), ready);
         println!("    📁 {}", project.path.display().to_string().dimmed());
         println!("    🔧 Template: {}", project.template.dimmed());
         println!("    📦 {}", installed.dimmed());
-        println!("");
+        if let Some(last_built) = &project.last_built {
+            println!("    🔨 Last built: {}", last_built.dimmed());
+        }
+        println!();
     }
     
     Ok(())
 }
 
-pub fn build_project(name: Option<String>) -> Result<()> {
-    let project_manager = ProjectManager::new()?;
+pub fn build_project(name: Option<String>, force: bool, install: Option<bool>, prompter: &Prompter) -> Result<()> {
+    let mut project_manager = ProjectManager::new()?;
     let path_manager = PathManager::new()?;
     
-    let project_name = match name {
-        Some(n) => n,
+    let mut project = match name {
+        Some(n) => project_manager.get_project(&n)?,
         None => {
             // Try to detect project in current directory
             let current_dir = std::env::current_dir()?;
-            if let Some(name) = current_dir.file_name().and_then(|s| s.to_str()) {
-                name.to_string()
-            } else {
-                return Err(anyhow::anyhow!("Could not determine project name. Please specify with: murex build <name>"));
-            }
+            project_manager.detect_project(&current_dir)?
         }
     };
+    let project_name = project.name.clone();
     
-    let project = project_manager.get_project(&project_name)?;
     println!("{} Building CLI utility: {}", "🔨".bright_yellow(), project_name.bright_blue());
     
-    project.build()?;
+    if project_manager.ensure_built(&mut project, force, &BuildLog::default())? {
+        println!("{} Successfully built: {}", "✅".bright_green(), project_name.bright_blue());
+    } else {
+        println!("{} {} is up to date (use --force to rebuild)", "✨".bright_green(), project_name.bright_blue());
+    }
     
-    println!("{} Successfully built: {}", "✅".bright_green(), project_name.bright_blue());
+    // Offer to install the project unless told explicitly
+    let install = match install {
+        Some(install) => install,
+        None => prompter.confirm("Install to make globally available?", true)?,
+    };
     
-    // Offer to install the project
-    let install = Confirm::new()
-        .with_prompt("Install to make globally available?")
-        .default(true)
-        .interact()?;
-        
     if install {
         path_manager.install_project(&project)?;
         println!("You can now run {} from anywhere!", project_name.bright_green());
@@ -225,7 +724,59 @@ pub fn build_project(name: Option<String>) -> Result<()> {
     Ok(())
 }
 
-pub fn remove_project(name: String) -> Result<()> {
+pub fn build_all_projects(force: bool, template: Option<String>, tags: Vec<String>, jobs: Option<usize>) -> Result<()> {
+    let mut project_manager = ProjectManager::new()?;
+    
+    let mut projects = Vec::new();
+    for project in project_manager.list_projects()? {
+        let template_matches = template.as_ref().is_none_or(|t| &project.template == t);
+        let tags_match = tags.iter().all(|tag| project.tags.contains(tag));
+        if template_matches && tags_match {
+            projects.push(project);
+        }
+    }
+    
+    if projects.is_empty() {
+        println!("{} No CLI utilities match.", "📋".bright_blue());
+        return Ok(());
+    }
+    
+    let jobs = jobs.unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+    println!("{} Building {} CLI utilities ({} at a time)", "🔨".bright_yellow(), projects.len(), jobs);
+    
+    let reports = project_manager.build_projects(&projects, force, jobs)?;
+    
+    let name_width = reports.iter().map(|r| r.name.len()).max().unwrap_or(0).max("PROJECT".len());
+    println!();
+    println!("{} Build summary:", "📊".bright_blue());
+    println!("  {:<name_width$}  {:<10}  DURATION", "PROJECT", "STATUS");
+    
+    let mut failures = Vec::new();
+    for report in &reports {
+        let status = match &report.result {
+            Ok(true) => format!("{:<10}", "built").bright_green(),
+            Ok(false) => format!("{:<10}", "up to date").dimmed(),
+            Err(e) => {
+                failures.push((&report.name, e));
+                format!("{:<10}", "failed").bright_red()
+            }
+        };
+        println!("  {:<name_width$}  {}  {:.1}s", report.name, status, report.duration.as_secs_f64());
+    }
+    
+    if failures.is_empty() {
+        return Ok(());
+    }
+    
+    println!();
+    for (name, error) in &failures {
+        println!("{} {}: {}", "❌".bright_red(), name.bright_blue(), error);
+    }
+    
+    Err(anyhow::anyhow!("{} of {} builds failed", failures.len(), reports.len()))
+}
+
+pub fn remove_project(name: String, prompter: &Prompter) -> Result<()> {
     let mut project_manager = ProjectManager::new()?;
     
     if !project_manager.project_exists(&name)? {
@@ -233,13 +784,13 @@ pub fn remove_project(name: String) -> Result<()> {
         return Ok(());
     }
     
-    let confirm = Confirm::new()
-        .with_prompt(&format!("Are you sure you want to remove '{}'?", name))
-        .default(false)
-        .interact()?;
-        
+    let confirm = prompter.confirm(&format!("Are you sure you want to remove '{}'?", name), false)?;
+    
     if !confirm {
         println!("Cancelled.");
+        if !prompter.is_interactive() {
+            println!("Pass {} to remove without prompting.", "--yes".bright_green());
+        }
         return Ok(());
     }
     
@@ -249,31 +800,154 @@ pub fn remove_project(name: String) -> Result<()> {
     Ok(())
 }
 
-pub fn handle_template_command(action: TemplateAction) -> Result<()> {
+pub fn handle_template_command(action: TemplateAction, prompter: &Prompter, format: OutputFormat) -> Result<()> {
     let mut template_manager = TemplateManager::new()?;
     
     match action {
         TemplateAction::List => {
-            let templates = template_manager.list_templates()?;
-            println!("{} Available templates:", "📋".bright_blue());
-            for template in templates {
-                println!("  - {}", template.bright_green());
+            let templates = template_manager.template_infos()?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&templates)?),
+                OutputFormat::Plain => {
+                    for template in templates {
+                        println!(
+                            "{}\t{}\t{}\t{}",
+                            template.name,
+                            template.scope,
+                            if template.shadowed_by.is_some() { "shadowed" } else { "active" },
+                            template.description.unwrap_or_default()
+                        );
+                    }
+                }
+                OutputFormat::Table => {
+                    println!("{} Available templates:", "📋".bright_blue());
+                    let width = templates.iter().map(|t| t.name.len()).max().unwrap_or(0);
+                    for template in templates {
+                        let description = template.description.unwrap_or_default();
+                        let (name, description) = match template.shadowed_by {
+                            Some(scope) => (
+                                template.name.dimmed(),
+                                format!("(shadowed by {} template) {}", scope, description).dimmed(),
+                            ),
+                            None => (template.name.bright_green(), description.normal()),
+                        };
+                        println!(
+                            "  - {:<width$}  {:<8}  {}",
+                            name,
+                            template.scope.to_string().dimmed(),
+                            description,
+                            width = width
+                        );
+                    }
+                }
+            }
+        }
+        TemplateAction::Show { name } => {
+            let details = template_manager.template_details(&name)?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&details)?),
+                OutputFormat::Plain => show_template_plain(&details),
+                OutputFormat::Table => show_template(&details),
             }
         }
-        TemplateAction::Add { name, path } => {
-            template_manager.add_template(name.clone(), path)?;
-            println!("{} Added template: {}", "✅".bright_green(), name.bright_blue());
+        TemplateAction::Add { name, source, git, reference, description } => {
+            if git || reference.is_some() || is_git_url(&source) {
+                let origin = template_manager.add_git_template(name.clone(), &source, reference, description)?;
+                println!(
+                    "{} Added template: {} ({} at {})",
+                    "✅".bright_green(),
+                    name.bright_blue(),
+                    origin.url,
+                    short_commit(&origin.commit).bright_yellow()
+                );
+            } else {
+                template_manager.add_template(name.clone(), PathBuf::from(source), description)?;
+                println!("{} Added template: {}", "✅".bright_green(), name.bright_blue());
+            }
+            print_shadowed_builtin(&name);
+        }
+        TemplateAction::AddPack { path, namespace } => {
+            let names = template_manager.add_pack(&path, namespace)?;
+            println!("{} Added {} template(s) from {}:", "✅".bright_green(), names.len(), path.display());
+            for name in names {
+                println!("  - {}", name.bright_blue());
+            }
+        }
+        TemplateAction::RemovePack { namespace } => {
+            let names = template_manager.remove_pack(&namespace)?;
+            println!("{} Removed pack {} ({} template(s))", "🗑️".bright_red(), namespace.bright_blue(), names.len());
+        }
+        TemplateAction::Check { name, build } => {
+            println!("{} Checking template: {}", "🔍".bright_blue(), name.bright_blue());
+            let problems = ProjectManager::new()?.check_template(&name, build)?;
+            if !problems.is_empty() {
+                for problem in &problems {
+                    println!("  {} {}", "❌".bright_red(), problem);
+                }
+                return Err(anyhow::anyhow!("Template '{}' has {} problem(s)", name, problems.len()));
+            }
+            
+            let checked = if build { "renders and builds" } else { "renders" };
+            println!("{} Template {} {} without problems", "✅".bright_green(), name.bright_blue(), checked);
+        }
+        TemplateAction::Update { name, reference } => {
+            let (previous, current) = template_manager.update_template(&name, reference)?;
+            if previous == current {
+                println!("{} Template {} is already at {}", "✅".bright_green(), name.bright_blue(), short_commit(&current).bright_yellow());
+            } else {
+                println!(
+                    "{} Updated template {}: {} → {}",
+                    "✅".bright_green(),
+                    name.bright_blue(),
+                    short_commit(&previous).dimmed(),
+                    short_commit(&current).bright_yellow()
+                );
+            }
         }
         TemplateAction::Remove { name } => {
             template_manager.remove_template(&name)?;
             println!("{} Removed template: {}", "🗑️".bright_red(), name.bright_blue());
         }
+        TemplateAction::Sync { project } => {
+            let report = ProjectManager::new()?.sync_project(&project, prompter)?;
+            print_sync_report(&project, &report)?;
+        }
+        TemplateAction::Save { project, name, path } => {
+            let project = ProjectManager::new()?.get_project(&project)?;
+            let name = name.unwrap_or_else(|| project.name.clone());
+            let path = match path {
+                Some(path) => path,
+                None => get_config_dir()?.join("templates").join(&name),
+            };
+            let binary = PathManager::new()?.find_project_binary(&project).ok();
+            
+            template_manager.save_project_as_template(&project, &name, &path, binary.as_deref())?;
+            println!("{} Saved {} as template: {}", "✅".bright_green(), project.name.bright_blue(), name.bright_blue());
+            println!("  📁 Location: {}", path.display());
+        }
+        TemplateAction::Eject { name, path, add } => {
+            let path = path.unwrap_or_else(|| PathBuf::from(&name));
+            template_manager.eject_template(&name, &path)?;
+            println!("{} Ejected template {} to {}", "✅".bright_green(), name.bright_blue(), path.display());
+            
+            match add {
+                Some(add) => {
+                    template_manager.add_template(add.clone(), path.canonicalize()?, None)?;
+                    println!("{} Added template: {}", "✅".bright_green(), add.bright_blue());
+                    print_shadowed_builtin(&add);
+                }
+                None => println!(
+                    "  💡 Register it with: murex template add <name> {}",
+                    path.display()
+                ),
+            }
+        }
     }
     
     Ok(())
 }
 
-pub fn handle_config_command(action: ConfigAction) -> Result<()> {
+pub fn handle_config_command(action: ConfigAction, prompter: &Prompter, format: OutputFormat) -> Result<()> {
     let mut config = Config::load()?;
     
     match action {
@@ -289,11 +963,11 @@ pub fn handle_config_command(action: ConfigAction) -> Result<()> {
                 println!("{} No templates found, using 'rust' as default.", "⚠️".bright_yellow());
                 "rust".to_string()
             } else {
-                let selection = Select::new()
-                    .with_prompt("What should be your default template?")
-                    .default(0)
-                    .items(&available_templates)
-                    .interact()?;
+                let default_index = available_templates
+                    .iter()
+                    .position(|t| t == &config.default_template)
+                    .unwrap_or(0);
+                let selection = prompter.select("What should be your default template?", &available_templates, default_index)?;
                 available_templates[selection].clone()
             };
             
@@ -301,18 +975,16 @@ pub fn handle_config_command(action: ConfigAction) -> Result<()> {
             let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
             let default_projects_dir = home_dir.join(".murex");
             
-            let projects_dir_input: String = Input::new()
-                .with_prompt("Where should murex store your CLI projects?")
-                .default(default_projects_dir.display().to_string())
-                .interact_text()?;
+            let projects_dir_input = prompter.input(
+                "Where should murex store your CLI projects?",
+                default_projects_dir.display().to_string(),
+                false,
+            )?;
             let projects_dir = PathBuf::from(projects_dir_input);
             
             // Auto-build preference
-            let auto_build = Confirm::new()
-                .with_prompt("Enable auto-build when creating projects?")
-                .default(false)
-                .interact()?;
-                
+            let auto_build = prompter.toggle("Enable auto-build when creating projects?", false)?;
+            
             // Editor preference
             let current_editor = std::env::var("EDITOR").unwrap_or_else(|_| "".to_string());
             let editor_prompt = if current_editor.is_empty() {
@@ -321,12 +993,8 @@ pub fn handle_config_command(action: ConfigAction) -> Result<()> {
                 format!("What is your preferred editor? (current: {})", current_editor)
             };
             
-            let editor_input: String = Input::new()
-                .with_prompt(&editor_prompt)
-                .default(current_editor.clone())
-                .allow_empty(true)
-                .interact_text()?;
-                
+            let editor_input = prompter.input(&editor_prompt, current_editor.clone(), true)?;
+            
             let editor = if editor_input.trim().is_empty() {
                 None
             } else {
@@ -361,6 +1029,16 @@ pub fn handle_config_command(action: ConfigAction) -> Result<()> {
             let path_manager = PathManager::new()?;
             path_manager.check_path_setup()?;
         }
+        ConfigAction::Show if format == OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&config)?);
+        }
+        ConfigAction::Show if format == OutputFormat::Plain => {
+            println!("default_template={}", config.default_template);
+            println!("projects_dir={}", config.projects_dir.display());
+            println!("bin_dir={}", config.bin_dir.display());
+            println!("auto_build={}", config.auto_build);
+            println!("editor={}", config.editor.as_deref().unwrap_or(""));
+        }
         ConfigAction::Show => {
             println!("{} Current configuration:", "⚙️".bright_blue());
             println!("  Default template: {}", config.default_template.bright_green());
@@ -383,27 +1061,30 @@ pub fn handle_config_command(action: ConfigAction) -> Result<()> {
             println!("{} Set {} = {}", "✅".bright_green(), key.bright_blue(), value.bright_green());
         }
         ConfigAction::Reset => {
-            let confirm = Confirm::new()
-                .with_prompt("Reset configuration to defaults?")
-                .default(false)
-                .interact()?;
-                
-            if confirm {
-                config = Config::default();
-                config.save()?;
-                println!("{} Configuration reset to defaults", "✅".bright_green());
+            let confirm = prompter.confirm("Reset configuration to defaults?", false)?;
+            
+            if !confirm {
+                println!("Cancelled.");
+                if !prompter.is_interactive() {
+                    println!("Pass {} to reset without prompting.", "--yes".bright_green());
+                }
+                return Ok(());
             }
+            
+            config = Config::default();
+            config.save()?;
+            println!("{} Configuration reset to defaults", "✅".bright_green());
         }
     }
     
     Ok(())
 }
 
-pub fn install_project(name: String) -> Result<()> {
-    let project_manager = ProjectManager::new()?;
+pub fn install_project(name: String, force: bool) -> Result<()> {
+    let mut project_manager = ProjectManager::new()?;
     let path_manager = PathManager::new()?;
     
-    let project = project_manager.get_project(&name)?;
+    let mut project = project_manager.get_project(&name)?;
     
     if !project.path.exists() {
         println!("{} Project directory does not exist: {}", "❌".bright_red(), project.path.display());
@@ -412,10 +1093,9 @@ pub fn install_project(name: String) -> Result<()> {
     
     println!("{} Installing CLI utility: {}", "📦".bright_blue(), name.bright_blue());
     
-    // Check if project is built
-    if path_manager.find_project_binary(&project).is_err() {
-        println!("  🔨 Project not built, building first...");
-        project.build()?;
+    // Rebuild first if the sources changed since the last build
+    if !project_manager.ensure_built(&mut project, force, &BuildLog::default())? {
+        println!("  ✨ Build is up to date");
     }
     
     path_manager.install_project(&project)?;
@@ -426,6 +1106,23 @@ pub fn install_project(name: String) -> Result<()> {
     Ok(())
 }
 
+pub fn run_project(name: String, args: Vec<String>, force: bool) -> Result<()> {
+    let mut project_manager = ProjectManager::new()?;
+    let path_manager = PathManager::new()?;
+    
+    let mut project = project_manager.get_project(&name)?;
+    
+    if !project.path.exists() {
+        return Err(anyhow::anyhow!("Project directory does not exist: {}", project.path.display()));
+    }
+    
+    // Build progress goes to stderr so the program's stdout stays clean
+    project_manager.ensure_built(&mut project, force, &BuildLog::stderr())?;
+    
+    let binary_path = path_manager.find_project_binary(&project)?;
+    utils::exec_program(&binary_path, &args)
+}
+
 pub fn uninstall_project(name: String) -> Result<()> {
     let path_manager = PathManager::new()?;
     
@@ -439,7 +1136,7 @@ pub fn uninstall_project(name: String) -> Result<()> {
 }
 
 pub fn generate_completions(shell: Shell) -> Result<()> {
-    use crate::main::Cli;
+    use crate::Cli;
     use clap::CommandFactory;
     
     let mut cmd = Cli::command();
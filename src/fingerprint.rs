@@ -0,0 +1,62 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories holding build output or dependencies rather than sources
+const IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules", "__pycache__", ".venv"];
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Compute a fingerprint of every source file under a project directory
+///
+/// The fingerprint covers relative paths and file contents, so it changes
+/// when a file is added, removed, renamed or edited but not when it is merely
+/// touched. Paths in `exclude` (typically the built binary) are skipped.
+pub fn compute(project_dir: &Path, exclude: &[PathBuf]) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(project_dir, exclude, &mut files)?;
+    files.sort();
+    
+    let mut hash = FNV_OFFSET;
+    for path in files {
+        let relative = path.strip_prefix(project_dir).unwrap_or(&path);
+        hash = fnv1a(hash, relative.to_string_lossy().as_bytes());
+        hash = fnv1a(hash, &[0]);
+        hash = fnv1a(hash, &fs::read(&path)?);
+        hash = fnv1a(hash, &[0]);
+    }
+    
+    Ok(format!("{:016x}", hash))
+}
+
+fn collect_files(dir: &Path, exclude: &[PathBuf], files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        
+        if exclude.contains(&path) {
+            continue;
+        }
+        
+        if file_type.is_dir() {
+            let ignored = entry.file_name().to_str().is_some_and(|name| IGNORED_DIRS.contains(&name));
+            if !ignored {
+                collect_files(&path, exclude, files)?;
+            }
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    
+    Ok(())
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::utils;
+
+/// Clone `url` into `dest` without checking anything out yet
+pub fn clone(url: &str, dest: &Path) -> Result<()> {
+    utils::ensure_tools(&["git"])?;
+    
+    let dest = dest.to_string_lossy();
+    run(None, &["clone", "--quiet", "--no-checkout", "--", url, &dest])?;
+    Ok(())
+}
+
+/// Fetch branches and tags from `origin`
+pub fn fetch(repo: &Path) -> Result<()> {
+    utils::ensure_tools(&["git"])?;
+    
+    run(Some(repo), &["fetch", "--quiet", "--tags", "--force", "--prune", "origin"])?;
+    Ok(())
+}
+
+/// Resolve a branch, tag or commit to a commit hash
+///
+/// Branches are looked up on `origin` first so a fetch is enough to move
+/// them; `None` means the remote's default branch.
+pub fn resolve(repo: &Path, reference: Option<&str>) -> Result<String> {
+    let candidates = match reference {
+        Some(reference) => vec![format!("origin/{}", reference), reference.to_string()],
+        None => vec!["origin/HEAD".to_string(), "HEAD".to_string()],
+    };
+    
+    for candidate in &candidates {
+        let spec = format!("{}^{{commit}}", candidate);
+        if let Ok(commit) = run(Some(repo), &["rev-parse", "--verify", "--quiet", &spec]) {
+            return Ok(commit);
+        }
+    }
+    
+    Err(anyhow::anyhow!(
+        "Could not find '{}' in the template repository",
+        reference.unwrap_or("the default branch")
+    ))
+}
+
+/// Check out `commit` as a detached HEAD, discarding local changes
+pub fn checkout(repo: &Path, commit: &str) -> Result<()> {
+    run(Some(repo), &["checkout", "--quiet", "--force", "--detach", commit])?;
+    Ok(())
+}
+
+/// Three-way merge file contents with `git merge-file`
+///
+/// Returns the merged contents, with conflict markers labelled `project`
+/// and `template` where both sides changed the same lines, and whether
+/// there were any conflicts.
+pub fn merge_file(ours: &[u8], base: &[u8], theirs: &[u8]) -> Result<(Vec<u8>, bool)> {
+    utils::ensure_tools(&["git"])?;
+    
+    let scratch = tempfile::tempdir()?;
+    let paths = [("ours", ours), ("base", base), ("theirs", theirs)].map(|(name, contents)| {
+        let path = scratch.path().join(name);
+        fs::write(&path, contents).map(|_| path)
+    });
+    let [ours, base, theirs] = paths;
+    
+    let output = Command::new("git")
+        .args(["merge-file", "-p", "-L", "project", "-L", "base", "-L", "template"])
+        .arg(ours?)
+        .arg(base?)
+        .arg(theirs?)
+        .output()?;
+    
+    // The exit code is the number of conflicts, or negative on error
+    match output.status.code() {
+        Some(0) => Ok((output.stdout, false)),
+        Some(code) if (1..=127).contains(&code) => Ok((output.stdout, true)),
+        _ => Err(anyhow::anyhow!("git merge-file failed:\n{}", String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+/// Run git, returning its trimmed stdout
+fn run(repo: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(repo) = repo {
+        command.arg("-C").arg(repo);
+    }
+    
+    let output = command.args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("git {} failed:\n{}", args[0], stderr.trim()));
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
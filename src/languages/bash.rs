@@ -0,0 +1,41 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::{Language, script_entrypoint};
+use crate::build_log::BuildLog;
+use crate::utils;
+
+/// Bash scripts run through `main.sh`
+pub struct Bash;
+
+impl Language for Bash {
+    fn name(&self) -> &'static str {
+        "bash"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["bash"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "main.sh"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["main.sh"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🐚 Building Bash project...");
+        let main_script = project_path.join("main.sh");
+        if main_script.exists() {
+            utils::make_executable(&main_script)?;
+        }
+        
+        Ok(())
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        script_entrypoint(project_path, project_name, "main.sh", "Bash")
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{Language, script_entrypoint};
+use crate::build_log::BuildLog;
+use crate::utils;
+
+/// Bun scripts run through `bun.js`
+pub struct Bun;
+
+impl Language for Bun {
+    fn name(&self) -> &'static str {
+        "bun"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["bun"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "bun.js"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["bun.lockb", "bun.lock", "bun.js"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🐰 Building Bun project...");
+        log.run(
+            Command::new("bun").args(["install"]).current_dir(project_path),
+            "Bun install failed",
+        )?;
+        
+        log.run(
+            Command::new("bun").args(["run", "start"]).current_dir(project_path),
+            "Bun start failed",
+        )?;
+        
+        // Make the entry script executable so it can be run directly
+        let bun_js = project_path.join("bun.js");
+        if bun_js.exists() {
+            utils::make_executable(&bun_js)?;
+        }
+        
+        Ok(())
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        script_entrypoint(project_path, project_name, "bun.js", "Bun")
+    }
+}
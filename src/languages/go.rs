@@ -0,0 +1,44 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::Language;
+use crate::build_log::BuildLog;
+
+/// Go projects using cobra
+pub struct Go;
+
+impl Language for Go {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["go"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "main.go"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["go.mod"]
+    }
+    
+    fn build(&self, project_path: &Path, project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🐹 Building Go project...");
+        log.run(
+            Command::new("go").args(["build", "-o", project_name]).current_dir(project_path),
+            "Go build failed",
+        )
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        let binary_path = project_path.join(project_name);
+        if binary_path.exists() {
+            Ok(binary_path)
+        } else {
+            Err(anyhow::anyhow!("No built binary found for Go project: {}", project_name))
+        }
+    }
+}
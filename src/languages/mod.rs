@@ -0,0 +1,72 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::build_log::BuildLog;
+
+mod bash;
+mod bun;
+mod go;
+mod node;
+mod python;
+mod rust;
+mod zsh;
+
+/// Everything murex knows about one built-in template
+pub trait Language: Sync {
+    /// Template name used on the command line and in the registry
+    fn name(&self) -> &'static str;
+    
+    /// Tools that must be available on PATH to build a project
+    fn required_tools(&self) -> &'static [&'static str];
+    
+    /// File opened by terminal editors, relative to the project directory
+    fn main_file(&self) -> &'static str;
+    
+    /// Files whose presence identifies an existing project of this language
+    fn markers(&self) -> &'static [&'static str];
+    
+    /// Build the project in place
+    fn build(&self, project_path: &Path, project_name: &str, log: &BuildLog) -> Result<()>;
+    
+    /// Locate the built binary or script to install
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf>;
+}
+
+static LANGUAGES: &[&dyn Language] = &[
+    &rust::Rust,
+    &python::Python,
+    // Bun before Node so a bun lockfile wins over a plain package.json
+    &bun::Bun,
+    &node::Node,
+    &go::Go,
+    &bash::Bash,
+    &zsh::Zsh,
+];
+
+/// All built-in languages
+pub fn all() -> &'static [&'static dyn Language] {
+    LANGUAGES
+}
+
+/// Look up a built-in language by template name
+pub fn get(name: &str) -> Option<&'static dyn Language> {
+    LANGUAGES.iter().copied().find(|language| language.name() == name)
+}
+
+/// Guess the language of an existing project directory from its marker files
+pub fn detect(dir: &Path) -> Option<&'static dyn Language> {
+    LANGUAGES
+        .iter()
+        .copied()
+        .find(|language| language.markers().iter().any(|marker| dir.join(marker).exists()))
+}
+
+/// Entrypoint for languages that install a script straight from the project directory
+fn script_entrypoint(project_path: &Path, project_name: &str, script: &str, label: &str) -> Result<PathBuf> {
+    let script_path = project_path.join(script);
+    if script_path.exists() {
+        Ok(script_path)
+    } else {
+        Err(anyhow::anyhow!("No {} found for {} project: {}", script, label, project_name))
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{Language, script_entrypoint};
+use crate::build_log::BuildLog;
+use crate::utils;
+
+/// Node.js projects using commander
+pub struct Node;
+
+impl Language for Node {
+    fn name(&self) -> &'static str {
+        "node"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["node", "npm"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "index.js"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["package.json", "index.js"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  📦 Building Node.js project...");
+        let package_json = project_path.join("package.json");
+        if package_json.exists() {
+            log.run(
+                Command::new("npm").args(["install"]).current_dir(project_path),
+                "npm install failed",
+            )?;
+            
+            // Try to run build script if it exists
+            let built = log.run(
+                Command::new("npm").args(["run", "build"]).current_dir(project_path),
+                "npm run build failed",
+            );
+            
+            // It's okay if build script doesn't exist
+            if built.is_err() {
+                log.info("  ⚠️  Build script failed (this might be expected)");
+            }
+        }
+        
+        // Make the entry script executable so it can be run directly
+        let index_js = project_path.join("index.js");
+        if index_js.exists() {
+            utils::make_executable(&index_js)?;
+        }
+        
+        Ok(())
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        script_entrypoint(project_path, project_name, "index.js", "Node.js")
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{Language, script_entrypoint};
+use crate::build_log::BuildLog;
+use crate::utils;
+
+/// Python scripts run through `main.py`
+pub struct Python;
+
+impl Language for Python {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["python3", "pip"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "main.py"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["pyproject.toml", "main.py", "requirements.txt"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🐍 Building Python project...");
+        // Check for requirements.txt and install dependencies
+        let requirements_path = project_path.join("requirements.txt");
+        if requirements_path.exists() {
+            log.run(
+                Command::new("pip").args(["install", "-r", "requirements.txt"]).current_dir(project_path),
+                "Python dependency installation failed",
+            )?;
+        }
+        
+        // Make the main script executable
+        let main_script = project_path.join("main.py");
+        if main_script.exists() {
+            utils::make_executable(&main_script)?;
+        }
+        
+        Ok(())
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        script_entrypoint(project_path, project_name, "main.py", "Python")
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::Language;
+use crate::build_log::BuildLog;
+
+/// Rust projects built with cargo
+pub struct Rust;
+
+impl Language for Rust {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["cargo"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "src/main.rs"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["Cargo.toml"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🦀 Building Rust project...");
+        log.run(
+            Command::new("cargo").args(["build", "--release"]).current_dir(project_path),
+            "Rust build failed",
+        )
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        let release_path = project_path.join("target/release").join(project_name);
+        let debug_path = project_path.join("target/debug").join(project_name);
+        
+        if release_path.exists() {
+            Ok(release_path)
+        } else if debug_path.exists() {
+            Ok(debug_path)
+        } else {
+            Err(anyhow::anyhow!("No built binary found for Rust project: {}", project_name))
+        }
+    }
+}
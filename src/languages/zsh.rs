@@ -0,0 +1,41 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::{Language, script_entrypoint};
+use crate::build_log::BuildLog;
+use crate::utils;
+
+/// Zsh scripts run through `main.zsh`
+pub struct Zsh;
+
+impl Language for Zsh {
+    fn name(&self) -> &'static str {
+        "zsh"
+    }
+    
+    fn required_tools(&self) -> &'static [&'static str] {
+        &["zsh"]
+    }
+    
+    fn main_file(&self) -> &'static str {
+        "main.zsh"
+    }
+    
+    fn markers(&self) -> &'static [&'static str] {
+        &["main.zsh"]
+    }
+    
+    fn build(&self, project_path: &Path, _project_name: &str, log: &BuildLog) -> Result<()> {
+        log.info("  🐚 Building Zsh project...");
+        let main_script = project_path.join("main.zsh");
+        if main_script.exists() {
+            utils::make_executable(&main_script)?;
+        }
+        
+        Ok(())
+    }
+    
+    fn entrypoint(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        script_entrypoint(project_path, project_name, "main.zsh", "Zsh")
+    }
+}
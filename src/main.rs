@@ -1,15 +1,23 @@
-use clap::{Parser, Subcommand};
+use clap::Parser;
 use anyhow::Result;
-use colored::*;
 
+mod build_log;
 mod cli;
 mod config;
+mod fingerprint;
+mod git;
+mod manifest;
 mod templates;
+mod languages;
 mod project;
 mod utils;
 mod path_manager;
+mod prompt;
+mod render;
+mod sync;
 
-use cli::Commands;
+use cli::{Commands, OutputFormat};
+use prompt::Prompter;
 
 #[derive(Parser)]
 #[command(name = "murex")]
@@ -18,35 +26,75 @@ use cli::Commands;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    
+    /// Answer yes to every confirmation prompt
+    #[arg(short, long, global = true)]
+    yes: bool,
+    
+    /// Never prompt; use defaults or explicit flags instead
+    #[arg(long, global = true)]
+    no_input: bool,
+    
+    /// Output format for commands that report data
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let prompter = Prompter::new(cli.yes, cli.no_input);
     
     match cli.command {
-        Commands::Init { name, template } => {
-            cli::init_project(name, template)?;
+        Commands::Init { name, template, tags, build, no_build, vars, dry_run, show_contents, no_hooks } => {
+            let options = cli::InitOptions {
+                template,
+                tags,
+                vars,
+                dry_run,
+                show_contents,
+                no_hooks,
+                build: match (build, no_build) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+            };
+            cli::init_project(name, options, &prompter)?;
+        }
+        Commands::Adopt { path, name, template, tags } => {
+            cli::adopt_project(path, name, template, tags)?;
         }
         Commands::List => {
-            cli::list_projects()?;
+            cli::list_projects(cli.format)?;
+        }
+        Commands::Build { all: true, force, template, tags, jobs, .. } => {
+            cli::build_all_projects(force, template, tags, jobs)?;
         }
-        Commands::Build { name } => {
-            cli::build_project(name)?;
+        Commands::Build { name, force, install, no_install, .. } => {
+            let install = match (install, no_install) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            cli::build_project(name, force, install, &prompter)?;
         }
         Commands::Remove { name } => {
-            cli::remove_project(name)?;
+            cli::remove_project(name, &prompter)?;
+        }
+        Commands::Install { name, force } => {
+            cli::install_project(name, force)?;
         }
-        Commands::Install { name } => {
-            cli::install_project(name)?;
+        Commands::Run { name, args, force } => {
+            cli::run_project(name, args, force)?;
         }
         Commands::Uninstall { name } => {
             cli::uninstall_project(name)?;
         }
         Commands::Template { action } => {
-            cli::handle_template_command(action)?;
+            cli::handle_template_command(action, &prompter, cli.format)?;
         }
         Commands::Config { action } => {
-            cli::handle_config_command(action)?;
+            cli::handle_config_command(action, &prompter, cli.format)?;
         }
         Commands::Completions { shell } => {
             cli::generate_completions(shell)?;
@@ -0,0 +1,389 @@
+use anyhow::Result;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::build_log::BuildLog;
+use crate::render::{self, Value, Variables};
+use crate::templates::TemplateScope;
+use crate::utils;
+
+/// File name of the manifest that lives at the root of a custom template
+pub const TEMPLATE_MANIFEST_FILE: &str = "murex-template.toml";
+
+/// File name of the manifest written into every project directory
+pub const PROJECT_MANIFEST_FILE: &str = "murex.toml";
+
+/// Metadata shipped alongside a custom template
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TemplateManifest {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub build: BuildRecipe,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<VariableSpec>,
+    #[serde(default, skip_serializing_if = "RenderRules::is_empty")]
+    pub render: RenderRules,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+}
+
+/// Commands a template runs in a new project's directory
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Hooks {
+    /// Shell commands run in order once the project's files are in place
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+}
+
+/// Which template files get variables rendered and which are copied verbatim
+///
+/// Patterns are globs; a pattern without a `/` matches a file name anywhere,
+/// otherwise it matches the path relative to the project root. Binary files
+/// are never rendered.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RenderRules {
+    /// Only render files matching one of these (every text file when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Never render files matching one of these
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// Compiled form of [`RenderRules`]
+pub struct RenderFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+/// A value the template asks for when a project is created
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariableSpec {
+    pub name: String,
+    /// Question shown when asking for a value (defaults to the name)
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default, rename = "type")]
+    pub kind: VariableKind,
+    /// Used when no value is given; string defaults may refer to other variables
+    #[serde(default)]
+    pub default: Option<toml::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableKind {
+    #[default]
+    String,
+    Bool,
+    List,
+}
+
+/// How to build a project and where to find the result
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BuildRecipe {
+    /// Shell commands run in order from the project directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    /// Path of the binary or script to install, relative to the project directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+    /// Tools that must be available on PATH before building
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Built-in language whose build steps are used when no commands are given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// The `murex.toml` a project carries so it can be used without the registry
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectManifest {
+    pub project: ProjectInfo,
+    #[serde(default, skip_serializing_if = "BuildRecipe::is_empty")]
+    pub build: BuildRecipe,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectInfo {
+    pub name: String,
+    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Version of the template the project was created or last synced from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_version: Option<String>,
+    /// Where the template was found when the project was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_scope: Option<TemplateScope>,
+    /// Directory of the project-local template the project was created from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_path: Option<PathBuf>,
+    /// Template variable values the project was rendered with
+    #[serde(default, skip_serializing_if = "Variables::is_empty")]
+    pub variables: Variables,
+}
+
+impl TemplateManifest {
+    /// Load the manifest from a template directory, if it has one
+    pub fn load(template_dir: &Path) -> Result<Option<Self>> {
+        let manifest_path = template_dir.join(TEMPLATE_MANIFEST_FILE);
+        
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        
+        let content = fs::read_to_string(&manifest_path)?;
+        Ok(Some(Self::parse(&content, &manifest_path)?))
+    }
+    
+    /// Parse manifest contents, naming `origin` in errors
+    pub fn parse(content: &str, origin: &Path) -> Result<Self> {
+        toml::from_str(content).map_err(|e| anyhow::anyhow!("Invalid {}: {}", origin.display(), e))
+    }
+    
+    pub fn save(&self, template_dir: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        fs::write(template_dir.join(TEMPLATE_MANIFEST_FILE), content)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for VariableKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableKind::String => write!(f, "string"),
+            VariableKind::Bool => write!(f, "bool"),
+            VariableKind::List => write!(f, "list"),
+        }
+    }
+}
+
+impl RenderRules {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+    
+    pub fn compile(&self) -> Result<RenderFilter> {
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&self.include)?)
+        };
+        
+        Ok(RenderFilter {
+            include,
+            exclude: build_glob_set(&self.exclude)?,
+        })
+    }
+}
+
+impl RenderFilter {
+    /// Whether the file at `relative` (from the project root) should be rendered
+    pub fn should_render(&self, relative: &Path) -> bool {
+        let matches = |set: &GlobSet| {
+            set.is_match(relative) || relative.file_name().is_some_and(|name| set.is_match(name))
+        };
+        
+        self.include.as_ref().is_none_or(matches) && !matches(&self.exclude)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid render pattern '{}': {}", pattern, e))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+impl VariableSpec {
+    pub fn prompt_text(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(&self.name)
+    }
+    
+    /// The declared default, rendered against the variables resolved so far
+    pub fn default_value(&self, variables: &Variables) -> Result<Value> {
+        let value = match (&self.default, self.kind) {
+            (None, VariableKind::String) => Value::String(String::new()),
+            (None, VariableKind::Bool) => Value::Bool(false),
+            (None, VariableKind::List) => Value::List(Vec::new()),
+            (Some(toml::Value::String(s)), _) => self.parse_value(&render::render_str(s, variables)?)?,
+            (Some(toml::Value::Boolean(b)), VariableKind::Bool) => Value::Bool(*b),
+            (Some(toml::Value::Array(items)), VariableKind::List) => Value::List(
+                items
+                    .iter()
+                    .map(|item| match item {
+                        toml::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect(),
+            ),
+            (Some(other), _) => {
+                return Err(anyhow::anyhow!("Default for variable '{}' does not match its type: {}", self.name, other));
+            }
+        };
+        
+        Ok(value)
+    }
+    
+    /// Parse a value given on the command line or typed at a prompt
+    pub fn parse_value(&self, raw: &str) -> Result<Value> {
+        match self.kind {
+            VariableKind::String => Ok(Value::String(raw.to_string())),
+            VariableKind::Bool => match raw.trim().to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" | "on" => Ok(Value::Bool(true)),
+                "false" | "no" | "n" | "0" | "off" | "" => Ok(Value::Bool(false)),
+                _ => Err(anyhow::anyhow!("Variable '{}' expects true or false, got '{}'", self.name, raw)),
+            },
+            VariableKind::List => Ok(Value::List(
+                raw.split(',')
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Load the manifest from a project directory, if it has one
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let manifest_path = project_dir.join(PROJECT_MANIFEST_FILE);
+        
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        
+        let content = fs::read_to_string(&manifest_path)?;
+        let manifest: ProjectManifest = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", manifest_path.display(), e))?;
+        Ok(Some(manifest))
+    }
+    
+    pub fn save(&self, project_dir: &Path) -> Result<()> {
+        fs::write(project_dir.join(PROJECT_MANIFEST_FILE), self.to_toml()?)?;
+        Ok(())
+    }
+    
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+impl BuildRecipe {
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty() && self.entrypoint.is_none() && self.requires.is_empty() && self.language.is_none()
+    }
+    
+    /// Render template variables into the commands and entrypoint
+    pub fn for_project(&self, variables: &Variables) -> Result<Self> {
+        Ok(Self {
+            commands: self.commands
+                .iter()
+                .map(|command| render::render_str(command, variables))
+                .collect::<Result<_>>()?,
+            entrypoint: self.entrypoint
+                .as_ref()
+                .map(|entrypoint| render::render_str(entrypoint, variables))
+                .transpose()?,
+            requires: self.requires.clone(),
+            language: self.language.clone(),
+        })
+    }
+    
+    /// Run the build commands for a project
+    pub fn run(&self, project_path: &Path, project_name: &str, log: &BuildLog) -> Result<()> {
+        utils::ensure_tools(&self.requires)?;
+        
+        for command in &self.commands {
+            let command = command.replace("{{PROJECT_NAME}}", project_name);
+            log.info(&format!("  ⚙️  {}", command));
+            
+            log.run(
+                shell_command(&command).current_dir(project_path),
+                &format!("Build command `{}` failed", command),
+            )?;
+        }
+        
+        // Make the entrypoint executable so scripts can be installed directly
+        if let Ok(entrypoint) = self.entrypoint_path(project_path, project_name) {
+            utils::make_executable(&entrypoint)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Resolve the entrypoint declared by the recipe
+    pub fn entrypoint_path(&self, project_path: &Path, project_name: &str) -> Result<PathBuf> {
+        let entrypoint = self.entrypoint
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No entrypoint declared for project: {}", project_name))?;
+        let path = project_path.join(entrypoint.replace("{{PROJECT_NAME}}", project_name));
+        
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(anyhow::anyhow!("No built binary found at {} for project: {}", path.display(), project_name))
+        }
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.post_create.is_empty()
+    }
+    
+    /// Render template variables into the commands
+    pub fn for_project(&self, variables: &Variables) -> Result<Self> {
+        Ok(Self {
+            post_create: self.post_create
+                .iter()
+                .map(|command| render::render_str(command, variables))
+                .collect::<Result<_>>()?,
+        })
+    }
+    
+    /// Run the post-create commands with their output shown, stopping at the first failure
+    pub fn run_post_create(&self, project_path: &Path) -> Result<()> {
+        for command in &self.post_create {
+            println!("  🪝 {}", command);
+            
+            let status = shell_command(command)
+                .current_dir(project_path)
+                .status()
+                .map_err(|e| anyhow::anyhow!("Hook `{}` could not be started: {}", command, e))?;
+            if !status.success() {
+                return Err(anyhow::anyhow!("Hook `{}` failed ({})", command, status));
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(unix)]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+    
+    #[cfg(not(unix))]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+}
@@ -1,11 +1,25 @@
 use anyhow::Result;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::fs;
 use std::env;
 use colored::*;
+use serde::Serialize;
 
 use crate::config::Config;
+use crate::languages;
 use crate::project::Project;
+use crate::templates::TemplateManager;
+use crate::utils;
+
+/// A project together with its derived on-disk state
+#[derive(Debug, Serialize)]
+pub struct ProjectStatus {
+    #[serde(flatten)]
+    pub project: Project,
+    pub directory_present: bool,
+    pub installed: bool,
+    pub binary_path: Option<PathBuf>,
+}
 
 pub struct PathManager {
     config: Config,
@@ -34,21 +48,8 @@ impl PathManager {
         let bin_name = &project.name;
         let target_path = self.config.bin_dir.join(bin_name);
         
-        // Remove existing link/file if it exists
-        if target_path.exists() {
-            fs::remove_file(&target_path)?;
-        }
-        
         // Create symlink on Unix systems, copy on Windows
-        #[cfg(unix)]
-        {
-            std::os::unix::fs::symlink(&binary_path, &target_path)?;
-        }
-        
-        #[cfg(not(unix))]
-        {
-            fs::copy(&binary_path, &target_path)?;
-        }
+        utils::create_link_or_copy(&binary_path, &target_path)?;
         
         println!("  📦 Installed {} to {}", bin_name.bright_blue(), target_path.display());
         
@@ -97,85 +98,50 @@ impl PathManager {
     
     /// Find the built binary for a project
     pub fn find_project_binary(&self, project: &Project) -> Result<PathBuf> {
-        match project.template.as_str() {
-            "rust" => {
-                let release_path = project.path.join("target/release").join(&project.name);
-                let debug_path = project.path.join("target/debug").join(&project.name);
-                
-                if release_path.exists() {
-                    Ok(release_path)
-                } else if debug_path.exists() {
-                    Ok(debug_path)
-                } else {
-                    Err(anyhow::anyhow!("No built binary found for Rust project: {}", project.name))
-                }
-            }
-            "go" => {
-                let binary_path = project.path.join(&project.name);
-                if binary_path.exists() {
-                    Ok(binary_path)
-                } else {
-                    Err(anyhow::anyhow!("No built binary found for Go project: {}", project.name))
-                }
-            }
-            "python" => {
-                let script_path = project.path.join("main.py");
-                if script_path.exists() {
-                    Ok(script_path)
-                } else {
-                    Err(anyhow::anyhow!("No main.py found for Python project: {}", project.name))
-                }
+        let recipe = project.manifest_recipe()?;
+        if let Some(recipe) = &recipe {
+            if recipe.entrypoint.is_some() {
+                return recipe.entrypoint_path(&project.path, &project.name);
             }
-            "node" => {
-                let script_path = project.path.join("index.js");
-                if script_path.exists() {
-                    Ok(script_path)
-                } else {
-                    Err(anyhow::anyhow!("No index.js found for Node.js project: {}", project.name))
-                }
-            }
-            "bash" => {
-                let script_path = project.path.join("main.sh");
-                if script_path.exists() {
-                    Ok(script_path)
-                } else {
-                    Err(anyhow::anyhow!("No main.sh found for Bash project: {}", project.name))
-                }
-            }
-            "zsh" => {
-                let script_path = project.path.join("main.zsh");
-                if script_path.exists() {
-                    Ok(script_path)
-                } else {
-                    Err(anyhow::anyhow!("No main.zsh found for Zsh project: {}", project.name))
-                }
-            }
-            "bun" => {
-                let script_path = project.path.join("bun.js");
-                if script_path.exists() {
-                    Ok(script_path)
-                } else {
-                    Err(anyhow::anyhow!("No bun.js found for Bun project: {}", project.name))
+        }
+        
+        let language = recipe.and_then(|recipe| recipe.language).unwrap_or_else(|| project.template.clone());
+        match languages::get(&language) {
+            Some(language) => language.entrypoint(&project.path, &project.name),
+            None => {
+                let recipe = TemplateManager::for_project(&project.path)?.build_recipe(&project.template)?;
+                match recipe.language.as_deref().and_then(languages::get) {
+                    Some(language) if recipe.entrypoint.is_none() => language.entrypoint(&project.path, &project.name),
+                    _ => recipe.entrypoint_path(&project.path, &project.name),
                 }
             }
-            _ => Err(anyhow::anyhow!("Unknown template: {}", project.template)),
         }
     }
     
+    /// Work out whether a project's directory, build and installed link exist
+    pub fn project_status(&self, project: Project) -> Result<ProjectStatus> {
+        let installed = self.list_installed()?.contains(&project.name);
+        let binary_path = self.find_project_binary(&project).ok();
+        
+        Ok(ProjectStatus {
+            directory_present: project.path.exists(),
+            installed,
+            binary_path,
+            project,
+        })
+    }
+    
     /// Check if binary exists
     pub fn binary_exists(&self, project: &Project) -> bool {
-        match self.find_project_binary(project) {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        self.find_project_binary(project).is_ok()
     }
     
     /// Show warning about PATH configuration
     pub fn show_path_warning(&self) {
-        println!("");
+        println!();
         println!("{} The murex bin directory is not in your PATH!", "⚠️".bright_yellow());
         println!("To use your CLI utilities from anywhere, add this to your shell profile:");
-        println!("");
+        println!();
         
         let bin_dir = self.config.bin_dir.display();
         let shell = env::var("SHELL").unwrap_or_else(|_| "bash".to_string());
@@ -188,21 +154,20 @@ impl PathManager {
             println!("  {}", format!("echo 'export PATH=\"{}:$PATH\"' >> ~/.bashrc", bin_dir).bright_green());
         }
         
-        println!("");
+        println!();
         println!("Then restart your terminal or run:");
         println!("  {}", "source ~/.bashrc  # or ~/.zshrc".bright_green());
-        println!("");
+        println!();
     }
     
     /// Get PATH setup instructions
+    #[allow(dead_code)]
     pub fn get_path_instructions(&self) -> String {
         let bin_dir = self.config.bin_dir.display();
         let shell = env::var("SHELL").unwrap_or_else(|_| "bash".to_string());
         
         if shell.contains("fish") {
             format!("fish_add_path {}", bin_dir)
-        } else if shell.contains("zsh") {
-            format!("export PATH=\"{}:$PATH\"", bin_dir)
         } else {
             format!("export PATH=\"{}:$PATH\"", bin_dir)
         }
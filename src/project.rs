@@ -1,12 +1,24 @@
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
-use std::path::PathBuf;
-use std::fs;
-use std::process::Command;
 use colored::*;
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::build_log::BuildLog;
 use crate::config::{Config, get_projects_file_path};
-use crate::templates::TemplateManager;
+use crate::fingerprint;
+use crate::languages;
+use crate::manifest::{BuildRecipe, ProjectInfo, ProjectManifest, PROJECT_MANIFEST_FILE};
+use crate::path_manager::PathManager;
+use crate::prompt::Prompter;
+use crate::render::{Value, Variables};
+use crate::sync::{self, SyncReport};
+use crate::templates::{self, RenderedFile, TemplateManager};
+use crate::utils;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Project {
@@ -15,6 +27,11 @@ pub struct Project {
     pub template: String,
     pub created_at: String,
     pub last_built: Option<String>,
+    /// Source fingerprint recorded after the last successful build
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Project {
@@ -27,173 +44,123 @@ impl Project {
             template,
             created_at: now,
             last_built: None,
+            fingerprint: None,
+            tags: Vec::new(),
         }
     }
     
-    pub fn build(&self) -> Result<()> {
-        if !self.path.exists() {
-            return Err(anyhow::anyhow!("Project directory does not exist: {}", self.path.display()));
+    /// Load a project from the `murex.toml` in its directory
+    pub fn from_dir(path: &Path) -> Result<Self> {
+        let manifest = ProjectManifest::load(path)?
+            .ok_or_else(|| anyhow::anyhow!("No {} found in {}", PROJECT_MANIFEST_FILE, path.display()))?;
+        
+        let mut project = Project::new(manifest.project.name, path.to_path_buf(), manifest.project.template);
+        project.tags = manifest.project.tags;
+        if let Some(created_at) = manifest.project.created_at {
+            project.created_at = created_at;
         }
         
-        match self.template.as_str() {
-            "rust" => self.build_rust(),
-            "python" => self.build_python(),
-            "node" => self.build_node(),
-            "go" => self.build_go(),
-            "bash" => self.build_bash(),
-            "zsh" => self.build_zsh(),
-            "bun" => self.build_bun(),
-            _ => Err(anyhow::anyhow!("Unknown template: {}", self.template)),
+        Ok(project)
+    }
+    
+    /// The `murex.toml` describing this project
+    pub fn manifest(&self, build: BuildRecipe) -> ProjectManifest {
+        ProjectManifest {
+            project: ProjectInfo {
+                name: self.name.clone(),
+                template: self.template.clone(),
+                created_at: Some(self.created_at.clone()),
+                tags: self.tags.clone(),
+                template_version: None,
+                template_scope: None,
+                template_path: None,
+                variables: Variables::new(),
+            },
+            build,
         }
     }
     
-    fn build_rust(&self) -> Result<()> {
-        println!("  🦀 Building Rust project...");
-        let output = Command::new("cargo")
-            .args(&["build", "--release"])
-            .current_dir(&self.path)
-            .output()?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Rust build failed:\n{}", stderr));
+    
+    /// Build recipe declared in the project's own `murex.toml`, if any
+    pub fn manifest_recipe(&self) -> Result<Option<BuildRecipe>> {
+        Ok(ProjectManifest::load(&self.path)?
+            .map(|manifest| manifest.build)
+            .filter(|recipe| !recipe.is_empty()))
+    }
+    
+    /// Prefer what the project directory says about itself over registry data
+    fn apply_manifest(mut self) -> Result<Self> {
+        if let Some(manifest) = ProjectManifest::load(&self.path)? {
+            self.template = manifest.project.template;
+            self.tags = manifest.project.tags;
         }
         
-        Ok(())
+        Ok(self)
     }
     
-    fn build_python(&self) -> Result<()> {
-        println!("  🐍 Building Python project...");
-        // Check for requirements.txt and install dependencies
-        let requirements_path = self.path.join("requirements.txt");
-        if requirements_path.exists() {
-            let output = Command::new("pip")
-                .args(&["install", "-r", "requirements.txt"])
-                .current_dir(&self.path)
-                .output()?;
-                
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(anyhow::anyhow!("Python dependency installation failed:\n{}", stderr));
-            }
+    pub fn build(&self, log: &BuildLog) -> Result<()> {
+        if !self.path.exists() {
+            return Err(anyhow::anyhow!("Project directory does not exist: {}", self.path.display()));
         }
         
-        // Make the main script executable
-        let main_script = self.path.join("main.py");
-        if main_script.exists() {
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let mut perms = fs::metadata(&main_script)?.permissions();
-                perms.set_mode(0o755);
-                fs::set_permissions(&main_script, perms)?;
+        let recipe = self.manifest_recipe()?;
+        if let Some(recipe) = &recipe {
+            if !recipe.commands.is_empty() {
+                log.info(&format!("  🧩 Building {} project...", self.template));
+                return recipe.run(&self.path, &self.name, log);
             }
         }
         
-        Ok(())
-    }
-    
-    fn build_node(&self) -> Result<()> {
-        println!("  📦 Building Node.js project...");
-        let package_json = self.path.join("package.json");
-        if package_json.exists() {
-            let output = Command::new("npm")
-                .args(&["install"])
-                .current_dir(&self.path)
-                .output()?;
-                
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(anyhow::anyhow!("npm install failed:\n{}", stderr));
-            }
-            
-            // Try to run build script if it exists
-            let output = Command::new("npm")
-                .args(&["run", "build"])
-                .current_dir(&self.path)
-                .output();
-                
-            // It's okay if build script doesn't exist
-            if let Ok(output) = output {
-                if !output.status.success() {
-                    println!("  ⚠️  Build script failed (this might be expected)");
-                }
+        let language = recipe.and_then(|recipe| recipe.language).unwrap_or_else(|| self.template.clone());
+        match languages::get(&language) {
+            Some(language) => {
+                utils::ensure_tools(language.required_tools())?;
+                language.build(&self.path, &self.name, log)
             }
+            None => self.build_custom(log),
         }
-        
-        Ok(())
     }
     
-    fn build_go(&self) -> Result<()> {
-        println!("  🐹 Building Go project...");
-        let output = Command::new("go")
-            .args(&["build", "-o", &self.name])
-            .current_dir(&self.path)
-            .output()?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Go build failed:\n{}", stderr));
-        }
+    fn build_custom(&self, log: &BuildLog) -> Result<()> {
+        log.info(&format!("  🧩 Building {} project...", self.template));
+        let recipe = TemplateManager::for_project(&self.path)?.build_recipe(&self.template)?;
         
-        Ok(())
-    }
-    
-    fn build_bash(&self) -> Result<()> {
-        println!("  🐚 Building Bash project...");
-        let main_script = self.path.join("main.sh");
-        if main_script.exists() {
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let mut perms = fs::metadata(&main_script)?.permissions();
-                perms.set_mode(0o755);
-                fs::set_permissions(&main_script, perms)?;
+        if let Some(language) = recipe.language.as_deref().and_then(languages::get) {
+            if recipe.commands.is_empty() {
+                utils::ensure_tools(language.required_tools())?;
+                return language.build(&self.path, &self.name, log);
             }
         }
         
-        Ok(())
+        recipe.run(&self.path, &self.name, log)
     }
     
-    fn build_zsh(&self) -> Result<()> {
-        println!("  🐚 Building Zsh project...");
-        let main_script = self.path.join("main.zsh");
-        if main_script.exists() {
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let mut perms = fs::metadata(&main_script)?.permissions();
-                perms.set_mode(0o755);
-                fs::set_permissions(&main_script, perms)?;
+    /// Build unless the sources are unchanged since the last successful build
+    ///
+    /// On success the fingerprint and `last_built` are updated; returns
+    /// whether a build actually ran.
+    pub fn build_if_stale(&mut self, force: bool, log: &BuildLog) -> Result<bool> {
+        let path_manager = PathManager::new()?;
+        
+        if !force && path_manager.binary_exists(self) {
+            let current = self.source_fingerprint(&path_manager)?;
+            if self.fingerprint.as_deref() == Some(current.as_str()) {
+                return Ok(false);
             }
         }
         
-        Ok(())
-    }
-    
-    fn build_bun(&self) -> Result<()> {
-        println!("  🐰 Building Bun project...");
-        let output = Command::new("bun")
-            .args(&["install"])
-            .current_dir(&self.path)
-            .output()?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Bun install failed:\n{}", stderr));
-        }
+        self.build(log)?;
         
-        let output = Command::new("bun")
-            .args(&["run", "start"])
-            .current_dir(&self.path)
-            .output()?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Bun start failed:\n{}", stderr));
-        }
+        self.fingerprint = Some(self.source_fingerprint(&path_manager)?);
+        self.last_built = Some(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string());
         
-        Ok(())
+        Ok(true)
+    }
+    
+    fn source_fingerprint(&self, path_manager: &PathManager) -> Result<String> {
+        // The built binary may live among the sources (e.g. Go), so leave it out
+        let exclude: Vec<PathBuf> = path_manager.find_project_binary(self).into_iter().collect();
+        fingerprint::compute(&self.path, &exclude)
     }
 }
 
@@ -240,6 +207,17 @@ impl ProjectRegistry {
         self.projects.len() != initial_len
     }
     
+    /// Replace a registered project in place, keeping its position
+    pub fn update_project(&mut self, project: &Project) -> bool {
+        match self.projects.iter_mut().find(|p| p.name == project.name) {
+            Some(existing) => {
+                *existing = project.clone();
+                true
+            }
+            None => false,
+        }
+    }
+    
     pub fn get_project(&self, name: &str) -> Option<&Project> {
         self.projects.iter().find(|p| p.name == name)
     }
@@ -249,6 +227,14 @@ impl ProjectRegistry {
     }
 }
 
+/// Outcome of one project's build in a batch
+pub struct BuildReport {
+    pub name: String,
+    /// Whether a build ran (`false` when already up to date), or why it failed
+    pub result: Result<bool>,
+    pub duration: Duration,
+}
+
 pub struct ProjectManager {
     registry: ProjectRegistry,
     config: Config,
@@ -262,18 +248,167 @@ impl ProjectManager {
         Ok(Self { registry, config })
     }
     
-    pub fn create_project(&mut self, name: String, template: String) -> Result<Project> {
+    /// Render everything a new project would contain, including its `murex.toml`, without writing it
+    pub fn plan_project(&self, name: String, template: String, tags: Vec<String>, variables: &Variables) -> Result<(Project, Vec<RenderedFile>)> {
         let project_path = self.config.projects_dir.join(&name);
         
         if project_path.exists() {
             return Err(anyhow::anyhow!("Project directory already exists: {}", project_path.display()));
         }
         
-        // Create project from template
         let template_manager = TemplateManager::new()?;
-        template_manager.create_project_from_template(&template, &project_path, &name)?;
+        let mut files = template_manager.render_template(&template, variables)?;
+        
+        let recipe = match template_manager.template_manifest(&template)? {
+            Some(manifest) => manifest.build.for_project(variables)?,
+            None => BuildRecipe::default(),
+        };
+        
+        let mut project = Project::new(name, project_path, template);
+        project.tags = tags;
+        
+        // Remember what the project was rendered from so `template sync` can update it later
+        let mut manifest = project.manifest(recipe);
+        manifest.project.template_version = Some(template_manager.template_version(&project.template)?);
+        manifest.project.template_scope = template_manager.template_scope(&project.template);
+        manifest.project.template_path = template_manager.local_template_path(&project.template);
+        manifest.project.variables = variables.clone();
+        
+        // The manifest is written by murex, so it replaces any file of the same name
+        files.retain(|file| file.path != Path::new(PROJECT_MANIFEST_FILE));
+        files.push(RenderedFile {
+            path: PathBuf::from(PROJECT_MANIFEST_FILE),
+            contents: manifest.to_toml()?.into_bytes(),
+            permissions: None,
+        });
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        
+        Ok((project, files))
+    }
+    
+    /// Create a project from a template
+    ///
+    /// Files are written to a scratch directory next to the final location
+    /// and renamed into place once complete, so a failure never leaves a
+    /// half-populated project behind. The registry is updated last.
+    pub fn create_project(&mut self, name: String, template: String, tags: Vec<String>, variables: &Variables) -> Result<Project> {
+        let (project, files) = self.plan_project(name, template, tags, variables)?;
+        
+        // Same filesystem as the destination, so the final rename is atomic
+        fs::create_dir_all(&self.config.projects_dir)?;
+        let scratch = tempfile::Builder::new()
+            .prefix(".murex-init-")
+            .tempdir_in(&self.config.projects_dir)?;
+        let staged = scratch.path().join(&project.name);
+        
+        templates::write_rendered(&staged, &files)?;
+        
+        let template_manager = TemplateManager::new()?;
+        template_manager.snapshot_template(&project.template, &template_manager.template_version(&project.template)?)?;
+        
+        if project.path.exists() {
+            return Err(anyhow::anyhow!("Project directory already exists: {}", project.path.display()));
+        }
+        fs::rename(&staged, &project.path)?;
         
-        let project = Project::new(name, project_path, template);
+        self.registry.add_project(project.clone());
+        if let Err(e) = self.registry.save() {
+            let _ = fs::remove_dir_all(&project.path);
+            return Err(e);
+        }
+        
+        Ok(project)
+    }
+    
+    /// Check a template for problems, rendering it into a temporary directory
+    ///
+    /// With `build` the rendered project is also built the way `murex build`
+    /// would and its entrypoint looked for. Returns every problem found.
+    pub fn check_template(&self, template: &str, build: bool) -> Result<Vec<String>> {
+        const PROJECT_NAME: &str = "example";
+        
+        let check = TemplateManager::new()?.check_template(template, PROJECT_NAME)?;
+        let mut problems = check.problems;
+        let Some(mut files) = check.files else {
+            return Ok(problems);
+        };
+        
+        let scratch = tempfile::tempdir()?;
+        let project = Project::new(PROJECT_NAME.to_string(), scratch.path().join(PROJECT_NAME), template.to_string());
+        
+        let mut manifest = project.manifest(check.build);
+        manifest.project.variables = check.variables;
+        files.retain(|file| file.path != Path::new(PROJECT_MANIFEST_FILE));
+        files.push(RenderedFile {
+            path: PathBuf::from(PROJECT_MANIFEST_FILE),
+            contents: manifest.to_toml()?.into_bytes(),
+            permissions: None,
+        });
+        
+        if let Err(e) = templates::write_rendered(&project.path, &files) {
+            problems.push(format!("Writing the rendered files failed: {}", e));
+            return Ok(problems);
+        }
+        
+        if build {
+            match project.build(&BuildLog::default()) {
+                Ok(()) => {
+                    if let Err(e) = PathManager::new()?.find_project_binary(&project) {
+                        problems.push(format!("Build succeeded but produced nothing to install: {}", e));
+                    }
+                }
+                Err(e) => problems.push(format!("Build failed: {}", e)),
+            }
+        }
+        
+        Ok(problems)
+    }
+    
+    /// Register an existing directory as a project without copying or scaffolding it
+    pub fn adopt_project(&mut self, path: &Path, name: Option<String>, template: Option<String>, tags: Vec<String>) -> Result<Project> {
+        if !utils::is_valid_project_dir(path) {
+            return Err(anyhow::anyhow!("Not a recognizable project directory: {}", path.display()));
+        }
+        
+        let path = path.canonicalize()?;
+        let manifest = ProjectManifest::load(&path)?;
+        
+        let name = match name {
+            Some(name) => name,
+            None => match &manifest {
+                Some(manifest) => manifest.project.name.clone(),
+                None => path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("Could not determine project name. Please specify with --name"))?,
+            },
+        };
+        utils::validate_project_name(&name)?;
+        
+        if self.registry.get_project(&name).is_some() {
+            return Err(anyhow::anyhow!("Project '{}' already exists", name));
+        }
+        
+        let template = match template {
+            Some(template) => {
+                if !TemplateManager::new()?.list_templates()?.contains(&template) {
+                    return Err(anyhow::anyhow!("Unknown template: {}", template));
+                }
+                template
+            }
+            None => match (&manifest, languages::detect(&path)) {
+                (Some(manifest), _) => manifest.project.template.clone(),
+                (None, Some(language)) => language.name().to_string(),
+                (None, None) => return Err(anyhow::anyhow!("Could not detect the project template. Please specify with --template")),
+            },
+        };
+        
+        let mut project = Project::new(name, path, template);
+        project.tags = match (tags.is_empty(), &manifest) {
+            (true, Some(manifest)) => manifest.project.tags.clone(),
+            _ => tags,
+        };
         self.registry.add_project(project.clone());
         self.registry.save()?;
         
@@ -281,19 +416,196 @@ impl ProjectManager {
     }
     
     pub fn list_projects(&self) -> Result<Vec<Project>> {
-        Ok(self.registry.list_projects().into_iter().cloned().collect())
+        // A broken manifest shouldn't hide every other project, so fall back to the registry entry
+        let mut projects: Vec<Project> = self.registry
+            .list_projects()
+            .into_iter()
+            .map(|project| match project.clone().apply_manifest() {
+                Ok(project) => project,
+                Err(e) => {
+                    eprintln!("{} Ignoring the {} of {}: {}", "⚠️".bright_yellow(), PROJECT_MANIFEST_FILE, project.name, e);
+                    project.clone()
+                }
+            })
+            .collect();
+        
+        // Unregistered projects in the projects directory are listed too, so a lost registry isn't fatal
+        let mut unregistered = Vec::new();
+        if self.config.projects_dir.is_dir() {
+            for entry in fs::read_dir(&self.config.projects_dir)? {
+                let path = entry?.path();
+                if !path.join(PROJECT_MANIFEST_FILE).is_file() || projects.iter().any(|project| project.path == path) {
+                    continue;
+                }
+                
+                let project = match Project::from_dir(&path) {
+                    Ok(project) => project,
+                    Err(e) => {
+                        eprintln!("{} Skipping {}: {}", "⚠️".bright_yellow(), path.display(), e);
+                        continue;
+                    }
+                };
+                if self.registry.get_project(&project.name).is_none() {
+                    unregistered.push(project);
+                }
+            }
+        }
+        
+        unregistered.sort_by(|a, b| a.name.cmp(&b.name));
+        projects.extend(unregistered);
+        Ok(projects)
     }
     
     pub fn get_project(&self, name: &str) -> Result<Project> {
-        self.registry
-            .get_project(name)
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))
+        if let Some(project) = self.registry.get_project(name) {
+            return project.clone().apply_manifest();
+        }
+        
+        // Fall back to a manifest in the projects directory so a lost registry isn't fatal
+        let project_path = self.config.projects_dir.join(name);
+        if project_path.join(PROJECT_MANIFEST_FILE).exists() {
+            return self.project_from_dir(&project_path);
+        }
+        
+        Err(anyhow::anyhow!("Project '{}' not found", name))
+    }
+    
+    /// Load the project whose `murex.toml` is in `dir`
+    ///
+    /// When the project is registered, its registry entry is used so the
+    /// recorded path, fingerprint and last build time are kept.
+    fn project_from_dir(&self, dir: &Path) -> Result<Project> {
+        let project = Project::from_dir(dir)?;
+        
+        match self.registry.get_project(&project.name) {
+            Some(registered) => registered.clone().apply_manifest(),
+            None => Ok(project),
+        }
+    }
+    
+    /// Build a project unless its sources are unchanged, persisting the result
+    ///
+    /// Returns whether a build actually ran.
+    pub fn ensure_built(&mut self, project: &mut Project, force: bool, log: &BuildLog) -> Result<bool> {
+        let built = project.build_if_stale(force, log)?;
+        if built {
+            self.record_build(project)?;
+        }
+        
+        Ok(built)
+    }
+    
+    /// Build several projects concurrently, at most `jobs` at a time
+    ///
+    /// Output from each build is streamed with the project name as a prefix.
+    /// Successful builds are recorded in the registry once all have finished.
+    pub fn build_projects(&mut self, projects: &[Project], force: bool, jobs: usize) -> Result<Vec<BuildReport>> {
+        let next = AtomicUsize::new(0);
+        let slots: Vec<Mutex<Option<(Project, BuildReport)>>> = projects.iter().map(|_| Mutex::new(None)).collect();
+        
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, projects.len().max(1)) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(project) = projects.get(index) else {
+                        break;
+                    };
+                    
+                    let mut project = project.clone();
+                    let log = BuildLog::prefixed(&project.name);
+                    let started = Instant::now();
+                    let result = project.build_if_stale(force, &log);
+                    
+                    let report = BuildReport {
+                        name: project.name.clone(),
+                        result,
+                        duration: started.elapsed(),
+                    };
+                    *slots[index].lock().unwrap() = Some((project, report));
+                });
+            }
+        });
+        
+        let mut reports = Vec::new();
+        for slot in slots {
+            let (project, report) = slot.into_inner().unwrap().expect("every project is built");
+            if matches!(report.result, Ok(true)) {
+                self.record_build(&project)?;
+            }
+            reports.push(report);
+        }
+        
+        Ok(reports)
+    }
+    
+    /// Save a project's build fingerprint and timestamp to the registry
+    pub fn record_build(&mut self, project: &Project) -> Result<()> {
+        if self.registry.update_project(project) {
+            self.registry.save()?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Bring a project up to date with the current version of its template
+    ///
+    /// The template is rendered at the version recorded in the project's
+    /// `murex.toml` and at its current version, and the difference is merged
+    /// into the project directory. Variables added to the template since are
+    /// asked for or take their defaults.
+    pub fn sync_project(&self, name: &str, prompter: &Prompter) -> Result<SyncReport> {
+        let project = self.get_project(name)?;
+        let mut manifest = ProjectManifest::load(&project.path)?
+            .ok_or_else(|| anyhow::anyhow!("No {} found in {}", PROJECT_MANIFEST_FILE, project.path.display()))?;
+        let from = manifest.project.template_version
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' doesn't record which version of its template it was created from", name))?;
+        
+        let template_manager = TemplateManager::for_project(&project.path)?;
+        let to = template_manager.template_version(&project.template)?;
+        if from == to {
+            return Ok(SyncReport { from, to, changes: Vec::new() });
+        }
+        
+        let mut variables = manifest.project.variables.clone();
+        variables
+            .entry("PROJECT_NAME".to_string())
+            .or_insert_with(|| Value::String(project.name.clone()));
+        
+        let mut base = template_manager.render_template_at(&project.template, &from, &variables)?;
+        let variables = template_manager.complete_variables(&project.template, variables, prompter)?;
+        let mut theirs = template_manager.render_template(&project.template, &variables)?;
+        
+        // murex.toml belongs to murex, not the template
+        base.retain(|file| file.path != Path::new(PROJECT_MANIFEST_FILE));
+        theirs.retain(|file| file.path != Path::new(PROJECT_MANIFEST_FILE));
+        
+        let changes = sync::apply(&project.path, &base, &theirs)?;
+        
+        template_manager.snapshot_template(&project.template, &to)?;
+        manifest.project.template_version = Some(to.clone());
+        manifest.project.variables = variables;
+        manifest.save(&project.path)?;
+        
+        Ok(SyncReport { from, to, changes })
+    }
+    
+    /// Find the project a directory belongs to, preferring its own manifest
+    pub fn detect_project(&self, dir: &Path) -> Result<Project> {
+        if dir.join(PROJECT_MANIFEST_FILE).exists() {
+            return self.project_from_dir(dir);
+        }
+        
+        match dir.file_name().and_then(|s| s.to_str()) {
+            Some(name) => self.get_project(name),
+            None => Err(anyhow::anyhow!("Could not determine project name. Please specify with: murex build <name>")),
+        }
     }
     
     pub fn remove_project(&mut self, name: &str) -> Result<()> {
         if let Some(project) = self.registry.get_project(name) {
-            if project.path.exists() {
+            // Adopted projects live outside projects_dir and are only unregistered
+            if project.path.exists() && project.path.starts_with(&self.config.projects_dir) {
                 fs::remove_dir_all(&project.path)?;
             }
         }
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use dialoguer::{Confirm, Input, Select};
+use std::io::IsTerminal;
+
+/// Asks the user questions, or answers them itself when prompting isn't possible
+///
+/// Every interactive `dialoguer` call goes through here so that `--yes`,
+/// `--no-input` and a non-TTY stdin behave the same across commands.
+#[derive(Debug, Clone, Copy)]
+pub struct Prompter {
+    interactive: bool,
+    assume_yes: bool,
+}
+
+impl Prompter {
+    pub fn new(assume_yes: bool, no_input: bool) -> Self {
+        Self {
+            interactive: !assume_yes && !no_input && std::io::stdin().is_terminal(),
+            assume_yes,
+        }
+    }
+    
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+    
+    /// Ask a yes/no question; `--yes` answers yes, otherwise the default is used
+    pub fn confirm(&self, prompt: &str, default: bool) -> Result<bool> {
+        if self.assume_yes {
+            return Ok(true);
+        }
+        
+        if !self.interactive {
+            return Ok(default);
+        }
+        
+        Ok(Confirm::new()
+            .with_prompt(prompt)
+            .default(default)
+            .interact()?)
+    }
+    
+    /// Ask a yes/no question about a preference; `--yes` keeps the default here
+    pub fn toggle(&self, prompt: &str, default: bool) -> Result<bool> {
+        if !self.interactive {
+            return Ok(default);
+        }
+        
+        Ok(Confirm::new()
+            .with_prompt(prompt)
+            .default(default)
+            .interact()?)
+    }
+    
+    /// Pick one of `items`, returning its index
+    pub fn select<T: ToString>(&self, prompt: &str, items: &[T], default: usize) -> Result<usize> {
+        if !self.interactive {
+            return Ok(default);
+        }
+        
+        Ok(Select::new()
+            .with_prompt(prompt)
+            .default(default)
+            .items(items)
+            .interact()?)
+    }
+    
+    /// Ask for free-form text
+    pub fn input(&self, prompt: &str, default: String, allow_empty: bool) -> Result<String> {
+        if !self.interactive {
+            return Ok(default);
+        }
+        
+        Ok(Input::new()
+            .with_prompt(prompt)
+            .default(default)
+            .allow_empty(allow_empty)
+            .interact_text()?)
+    }
+}
@@ -0,0 +1,469 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A template variable's value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    String(String),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+impl Value {
+    /// Whether `{{#if}}` considers the value set
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::String(s) => !s.is_empty() && s != "false",
+            Value::Bool(b) => *b,
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::List(items) => write!(f, "{}", items.join(", ")),
+        }
+    }
+}
+
+pub type Variables = BTreeMap<String, Value>;
+
+/// A parsed template
+///
+/// The syntax is a small subset of Handlebars:
+///
+/// - `{{name}}` inserts a variable
+/// - `{{#if name}}...{{else}}...{{/if}}` and `{{#unless name}}...{{/unless}}`
+///   keep a section depending on whether the variable is set
+/// - `{{#each name}}...{{this}}...{{/each}}` repeats a section per list item
+/// - `\{{` produces a literal `{{`
+///
+/// Anything between braces that isn't a plain identifier (for example
+/// `${{ secrets.TOKEN }}` in a CI file) is left untouched, as are
+/// identifiers with no value, so existing files survive rendering.
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Variable(String),
+    If {
+        name: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        name: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Variable(String),
+    Open { block: String, name: String },
+    Else,
+    Close(String),
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source);
+        let mut position = 0;
+        let nodes = parse_nodes(&tokens, &mut position, None)?;
+        Ok(Self { nodes })
+    }
+    
+    pub fn render(&self, variables: &Variables) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, variables, None, &mut output);
+        output
+    }
+    
+    /// Names of every variable the template refers to, including block conditions
+    pub fn variables_used(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        collect_variables(&self.nodes, &mut names);
+        names
+    }
+}
+
+/// Parse and render in one go
+pub fn render_str(source: &str, variables: &Variables) -> Result<String> {
+    Ok(Template::parse(source)?.render(variables))
+}
+
+/// Whether `s` can be used as a variable name
+pub fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = source;
+    
+    while let Some(start) = rest.find("{{") {
+        // An escaped opening brace pair is emitted literally
+        if rest[..start].ends_with('\\') {
+            text.push_str(&rest[..start - 1]);
+            text.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let inner = rest[start + 2..start + end].trim();
+        
+        let token = if let Some(open) = inner.strip_prefix('#') {
+            let mut parts = open.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(block @ ("if" | "unless" | "each")), Some(name), None) if is_identifier(name) => {
+                    Some(Token::Open { block: block.to_string(), name: name.to_string() })
+                }
+                _ => None,
+            }
+        } else if let Some(block @ ("if" | "unless" | "each")) = inner.strip_prefix('/') {
+            Some(Token::Close(block.to_string()))
+        } else if inner == "else" {
+            Some(Token::Else)
+        } else if is_identifier(inner) {
+            Some(Token::Variable(inner.to_string()))
+        } else {
+            None
+        };
+        
+        match token {
+            Some(token) => {
+                text.push_str(&rest[..start]);
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(token);
+            }
+            None => text.push_str(&rest[..start + end + 2]),
+        }
+        
+        rest = &rest[start + end + 2..];
+    }
+    
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    
+    strip_standalone_blocks(&mut tokens);
+    tokens
+}
+
+/// Remove the line a block tag sits on when the tag is alone on it
+///
+/// This keeps `{{#if x}}` / `{{/if}}` lines from leaving blank lines behind.
+fn strip_standalone_blocks(tokens: &mut [Token]) {
+    let is_block = |token: &Token| matches!(token, Token::Open { .. } | Token::Else | Token::Close(_));
+    
+    let standalone: Vec<usize> = (0..tokens.len())
+        .filter(|&i| is_block(&tokens[i]))
+        .filter(|&i| {
+            let starts_line = match i.checked_sub(1).map(|prev| &tokens[prev]) {
+                None => true,
+                Some(Token::Text(text)) => match text.rfind('\n') {
+                    Some(newline) => text[newline + 1..].trim().is_empty(),
+                    None => i == 1 && text.trim().is_empty(),
+                },
+                Some(_) => false,
+            };
+            let ends_line = match tokens.get(i + 1) {
+                None => true,
+                Some(Token::Text(text)) => match text.find('\n') {
+                    Some(newline) => text[..newline].trim().is_empty(),
+                    None => i + 2 == tokens.len() && text.trim().is_empty(),
+                },
+                Some(_) => false,
+            };
+            starts_line && ends_line
+        })
+        .collect();
+        
+    for i in standalone {
+        if let Some(Token::Text(text)) = i.checked_sub(1).map(|prev| &mut tokens[prev]) {
+            let keep = text.rfind('\n').map_or(0, |newline| newline + 1);
+            text.truncate(keep);
+        }
+        if let Some(Token::Text(text)) = tokens.get_mut(i + 1) {
+            let skip = text.find('\n').map_or(text.len(), |newline| newline + 1);
+            text.drain(..skip);
+        }
+    }
+}
+
+fn parse_nodes(tokens: &[Token], position: &mut usize, block: Option<&str>) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    
+    while let Some(token) = tokens.get(*position) {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.clone())),
+            Token::Variable(name) => nodes.push(Node::Variable(name.clone())),
+            Token::Open { block: kind, name } => {
+                *position += 1;
+                let body = parse_nodes(tokens, position, Some(kind))?;
+                let mut otherwise = Vec::new();
+                
+                if matches!(tokens.get(*position), Some(Token::Else)) {
+                    if kind == "each" {
+                        return Err(anyhow::anyhow!("{{{{else}}}} is not supported inside {{{{#each}}}}"));
+                    }
+                    *position += 1;
+                    otherwise = parse_nodes(tokens, position, Some(kind))?;
+                }
+                
+                match tokens.get(*position) {
+                    Some(Token::Close(closing)) if closing == kind => {}
+                    _ => return Err(anyhow::anyhow!("Unclosed {{{{#{} {}}}}}", kind, name)),
+                }
+                
+                nodes.push(match kind.as_str() {
+                    "each" => Node::Each { name: name.clone(), body },
+                    _ => Node::If {
+                        name: name.clone(),
+                        negate: kind == "unless",
+                        then: body,
+                        otherwise,
+                    },
+                });
+            }
+            Token::Else => {
+                if block.is_none() {
+                    return Err(anyhow::anyhow!("{{{{else}}}} outside of a block"));
+                }
+                return Ok(nodes);
+            }
+            Token::Close(closing) => {
+                if block != Some(closing.as_str()) {
+                    return Err(anyhow::anyhow!("Unexpected {{{{/{}}}}}", closing));
+                }
+                return Ok(nodes);
+            }
+        }
+        
+        *position += 1;
+    }
+    
+    match block {
+        Some(kind) => Err(anyhow::anyhow!("Unclosed {{{{#{}}}}} block", kind)),
+        None => Ok(nodes),
+    }
+}
+
+fn collect_variables(nodes: &[Node], names: &mut BTreeSet<String>) {
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Variable(name) => {
+                if name != "this" {
+                    names.insert(name.clone());
+                }
+            }
+            Node::If { name, then, otherwise, .. } => {
+                names.insert(name.clone());
+                collect_variables(then, names);
+                collect_variables(otherwise, names);
+            }
+            Node::Each { name, body } => {
+                names.insert(name.clone());
+                collect_variables(body, names);
+            }
+        }
+    }
+}
+
+fn render_nodes(nodes: &[Node], variables: &Variables, this: Option<&str>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(name) => match (name.as_str(), this, variables.get(name)) {
+                ("this", Some(item), _) => output.push_str(item),
+                (_, _, Some(value)) => output.push_str(&value.to_string()),
+                // Leave unknown placeholders as they were
+                _ => {
+                    output.push_str("{{");
+                    output.push_str(name);
+                    output.push_str("}}");
+                }
+            },
+            Node::If { name, negate, then, otherwise } => {
+                let set = variables.get(name).is_some_and(|value| value.is_truthy());
+                let branch = if set != *negate { then } else { otherwise };
+                render_nodes(branch, variables, this, output);
+            }
+            Node::Each { name, body } => {
+                let items = match variables.get(name) {
+                    Some(Value::List(items)) => items.clone(),
+                    Some(value) if value.is_truthy() => vec![value.to_string()],
+                    _ => Vec::new(),
+                };
+                for item in &items {
+                    render_nodes(body, variables, Some(item), output);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn vars(pairs: &[(&str, Value)]) -> Variables {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+    }
+    
+    fn render(source: &str, variables: &Variables) -> String {
+        render_str(source, variables).unwrap()
+    }
+    
+    fn string(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+    
+    fn list(items: &[&str]) -> Value {
+        Value::List(items.iter().map(|item| item.to_string()).collect())
+    }
+    
+    #[test]
+    fn substitutes_variables() {
+        let variables = vars(&[("name", string("demo"))]);
+        assert_eq!(render("hello {{name}}, {{ name }}!", &variables), "hello demo, demo!");
+    }
+    
+    #[test]
+    fn leaves_unknown_variables_alone() {
+        assert_eq!(render("{{missing}} stays", &Variables::new()), "{{missing}} stays");
+    }
+    
+    #[test]
+    fn passes_through_non_identifiers() {
+        let variables = vars(&[("secrets", string("x"))]);
+        let source = "token: ${{ secrets.TOKEN }} and {{ not an identifier }} and {{#if}}";
+        assert_eq!(render(source, &variables), source);
+    }
+    
+    #[test]
+    fn unterminated_braces_are_text() {
+        let variables = vars(&[("name", string("demo"))]);
+        assert_eq!(render("{{name}} {{name", &variables), "demo {{name");
+    }
+    
+    #[test]
+    fn escaped_braces_are_literal() {
+        let variables = vars(&[("name", string("demo"))]);
+        assert_eq!(render("\\{{name}} {{name}}", &variables), "{{name}} demo");
+    }
+    
+    #[test]
+    fn if_else_and_unless() {
+        let source = "{{#if on}}yes{{else}}no{{/if}} {{#unless on}}off{{/unless}}";
+        assert_eq!(render(source, &vars(&[("on", Value::Bool(true))])), "yes ");
+        assert_eq!(render(source, &vars(&[("on", Value::Bool(false))])), "no off");
+        assert_eq!(render(source, &Variables::new()), "no off");
+        assert_eq!(render(source, &vars(&[("on", string("false"))])), "no off");
+        assert_eq!(render(source, &vars(&[("on", list(&[]))])), "no off");
+    }
+    
+    #[test]
+    fn each_repeats_per_item() {
+        let variables = vars(&[("items", list(&["a", "b"]))]);
+        assert_eq!(render("{{#each items}}[{{this}}]{{/each}}", &variables), "[a][b]");
+        assert_eq!(render("{{#each missing}}x{{/each}}", &variables), "");
+    }
+    
+    #[test]
+    fn each_over_a_scalar_runs_once() {
+        let variables = vars(&[("item", string("only"))]);
+        assert_eq!(render("{{#each item}}{{this}}{{/each}}", &variables), "only");
+    }
+    
+    #[test]
+    fn nested_blocks() {
+        let variables = vars(&[
+            ("items", list(&["a", "b"])),
+            ("loud", Value::Bool(true)),
+            ("quiet", Value::Bool(false)),
+        ]);
+        let source = "{{#each items}}{{#if loud}}{{this}}!{{else}}{{this}}{{/if}}{{/each}}";
+        assert_eq!(render(source, &variables), "a!b!");
+        
+        let source = "{{#if loud}}{{#each items}}{{#unless quiet}}<{{this}}>{{/unless}}{{/each}}{{/if}}";
+        assert_eq!(render(source, &variables), "<a><b>");
+    }
+    
+    #[test]
+    fn strips_standalone_block_lines() {
+        let variables = vars(&[("on", Value::Bool(true))]);
+        let source = "start\n  {{#if on}}\nkept\n  {{else}}\ndropped\n  {{/if}}\nend\n";
+        assert_eq!(render(source, &variables), "start\nkept\nend\n");
+    }
+    
+    #[test]
+    fn strips_standalone_lines_at_start_and_end_of_file() {
+        let variables = vars(&[("on", Value::Bool(true))]);
+        assert_eq!(render("{{#if on}}\nbody\n{{/if}}\n", &variables), "body\n");
+        assert_eq!(render("{{#if on}}\nbody\n{{/if}}", &variables), "body\n");
+        assert_eq!(render("  {{#if on}}\nbody\n{{/if}}  ", &variables), "body\n");
+        assert_eq!(render("{{#if off}}\nbody\n{{/if}}\n", &variables), "");
+    }
+    
+    #[test]
+    fn keeps_inline_blocks_on_their_line() {
+        let variables = vars(&[("on", Value::Bool(true))]);
+        assert_eq!(render("a {{#if on}}b{{/if}} c\n", &variables), "a b c\n");
+        assert_eq!(render("{{#if on}}b{{/if}}\nnext\n", &variables), "b\nnext\n");
+    }
+    
+    #[test]
+    fn strips_standalone_lines_in_nested_blocks() {
+        let variables = vars(&[("items", list(&["a", "b"])), ("on", Value::Bool(true))]);
+        let source = "{{#each items}}\n{{#if on}}\n- {{this}}\n{{/if}}\n{{/each}}\n";
+        assert_eq!(render(source, &variables), "- a\n- b\n");
+    }
+    
+    #[test]
+    fn rejects_unclosed_blocks() {
+        assert!(Template::parse("{{#if on}}body").is_err());
+        assert!(Template::parse("{{#each items}}{{#if on}}{{/each}}").is_err());
+    }
+    
+    #[test]
+    fn rejects_mismatched_and_stray_tags() {
+        assert!(Template::parse("{{#if on}}body{{/each}}").is_err());
+        assert!(Template::parse("body{{/if}}").is_err());
+        assert!(Template::parse("{{else}}").is_err());
+    }
+    
+    #[test]
+    fn rejects_else_inside_each() {
+        assert!(Template::parse("{{#each items}}a{{else}}b{{/each}}").is_err());
+    }
+    
+    #[test]
+    fn lists_variables_used() {
+        let template = Template::parse("{{a}} {{#if b}}{{#each c}}{{this}}{{d}}{{/each}}{{else}}{{e}}{{/if}} {{ x.y }}").unwrap();
+        let used: Vec<String> = template.variables_used().into_iter().collect();
+        assert_eq!(used, ["a", "b", "c", "d", "e"]);
+    }
+}
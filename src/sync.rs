@@ -0,0 +1,266 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git;
+use crate::templates::{self, RenderedFile};
+
+/// What `template sync` did with one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// New in the template and written to the project
+    Added,
+    /// Changed only in the template, so replaced in the project
+    Updated,
+    /// Changed in both, merged without conflicts
+    Merged,
+    /// Changed in both, written with conflict markers
+    Conflict,
+    /// Removed from the template and unchanged in the project, so deleted
+    Removed,
+    /// Left alone because the project deleted or changed it
+    Skipped,
+}
+
+/// One file touched by a sync
+#[derive(Debug)]
+pub struct SyncChange {
+    pub path: PathBuf,
+    pub action: SyncAction,
+    /// Why the file was skipped or couldn't be merged normally
+    pub note: Option<String>,
+}
+
+/// Outcome of syncing a project with its template
+#[derive(Debug)]
+pub struct SyncReport {
+    pub from: String,
+    pub to: String,
+    pub changes: Vec<SyncChange>,
+}
+
+impl SyncReport {
+    pub fn conflicts(&self) -> usize {
+        self.changes.iter().filter(|change| change.action == SyncAction::Conflict).count()
+    }
+}
+
+/// Apply the differences between two renders of a template to a project
+///
+/// `base` is the template as the project was last rendered from it and
+/// `theirs` the template now. Each file the template changed is merged into
+/// `project_dir` the way `git merge` would: untouched project files take the
+/// new version, files changed on both sides are merged with conflict markers
+/// where the changes overlap.
+pub fn apply(project_dir: &Path, base: &[RenderedFile], theirs: &[RenderedFile]) -> Result<Vec<SyncChange>> {
+    let base: BTreeMap<&Path, &RenderedFile> = base.iter().map(|file| (file.path.as_path(), file)).collect();
+    let theirs: BTreeMap<&Path, &RenderedFile> = theirs.iter().map(|file| (file.path.as_path(), file)).collect();
+    let paths: BTreeSet<&Path> = base.keys().chain(theirs.keys()).copied().collect();
+    
+    let mut changes = Vec::new();
+    
+    for path in paths {
+        let base = base.get(path).map(|file| file.contents.as_slice());
+        let theirs = theirs.get(path).copied();
+        if base == theirs.map(|file| file.contents.as_slice()) {
+            continue;
+        }
+        
+        let target = project_dir.join(path);
+        let ours = if target.is_file() { Some(fs::read(&target)?) } else { None };
+        
+        let (action, note) = match (base, theirs, ours.as_deref()) {
+            // Already matches the new template
+            (_, Some(theirs), Some(ours)) if ours == theirs.contents => continue,
+            (_, None, None) => continue,
+            (None, Some(theirs), None) => {
+                templates::write_rendered(project_dir, std::slice::from_ref(theirs))?;
+                (SyncAction::Added, None)
+            }
+            (Some(_), Some(_), None) => (SyncAction::Skipped, Some("deleted in the project".to_string())),
+            (Some(base), Some(theirs), Some(ours)) if ours == base => {
+                templates::write_rendered(project_dir, std::slice::from_ref(theirs))?;
+                (SyncAction::Updated, None)
+            }
+            (base, Some(theirs), Some(ours)) => merge(&target, ours, base.unwrap_or_default(), theirs)?,
+            (Some(base), None, Some(ours)) if ours == base => {
+                fs::remove_file(&target)?;
+                (SyncAction::Removed, None)
+            }
+            (Some(_), None, Some(_)) => (SyncAction::Skipped, Some("removed from the template but changed in the project".to_string())),
+            (None, None, Some(_)) => continue,
+        };
+        
+        changes.push(SyncChange {
+            path: path.to_path_buf(),
+            action,
+            note,
+        });
+    }
+    
+    Ok(changes)
+}
+
+/// Merge a file changed on both sides into `target`
+fn merge(target: &Path, ours: &[u8], base: &[u8], theirs: &RenderedFile) -> Result<(SyncAction, Option<String>)> {
+    let all_text = [ours, base, theirs.contents.as_slice()]
+        .iter()
+        .all(|contents| templates::as_text(contents).is_some());
+    
+    // Conflict markers would corrupt a binary file, so put the template's version next to it
+    if !all_text {
+        let mut file_name = target.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".template");
+        fs::write(target.with_file_name(&file_name), &theirs.contents)?;
+        return Ok((
+            SyncAction::Conflict,
+            Some(format!("binary file, template version saved as {}", file_name.to_string_lossy())),
+        ));
+    }
+    
+    let (merged, conflicts) = git::merge_file(ours, base, &theirs.contents)?;
+    fs::write(target, merged)?;
+    
+    if conflicts {
+        Ok((SyncAction::Conflict, None))
+    } else {
+        Ok((SyncAction::Merged, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn file(path: &str, contents: &str) -> RenderedFile {
+        RenderedFile {
+            path: PathBuf::from(path),
+            contents: contents.as_bytes().to_vec(),
+            permissions: None,
+        }
+    }
+    
+    fn binary(path: &str, contents: &[u8]) -> RenderedFile {
+        RenderedFile {
+            path: PathBuf::from(path),
+            contents: contents.to_vec(),
+            permissions: None,
+        }
+    }
+    
+    /// A project directory holding `files`
+    fn project(files: &[(&str, &[u8])]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+    
+    fn read(dir: &tempfile::TempDir, path: &str) -> String {
+        fs::read_to_string(dir.path().join(path)).unwrap()
+    }
+    
+    fn only_change(changes: &[SyncChange]) -> (&Path, SyncAction) {
+        assert_eq!(changes.len(), 1, "{:?}", changes);
+        (changes[0].path.as_path(), changes[0].action)
+    }
+    
+    #[test]
+    fn adds_new_template_files() {
+        let dir = project(&[]);
+        let changes = apply(dir.path(), &[], &[file("src/new.rs", "new\n")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("src/new.rs"), SyncAction::Added));
+        assert_eq!(read(&dir, "src/new.rs"), "new\n");
+    }
+    
+    #[test]
+    fn updates_files_the_project_left_alone() {
+        let dir = project(&[("a.txt", b"one\n")]);
+        let changes = apply(dir.path(), &[file("a.txt", "one\n")], &[file("a.txt", "two\n")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("a.txt"), SyncAction::Updated));
+        assert_eq!(read(&dir, "a.txt"), "two\n");
+    }
+    
+    #[test]
+    fn merges_separate_changes() {
+        let dir = project(&[("a.txt", b"one changed\ntwo\nthree\nfour\nfive\n")]);
+        let base = [file("a.txt", "one\ntwo\nthree\nfour\nfive\n")];
+        let theirs = [file("a.txt", "one\ntwo\nthree\nfour\nfive changed\n")];
+        let changes = apply(dir.path(), &base, &theirs).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("a.txt"), SyncAction::Merged));
+        assert_eq!(read(&dir, "a.txt"), "one changed\ntwo\nthree\nfour\nfive changed\n");
+    }
+    
+    #[test]
+    fn marks_overlapping_changes_as_conflicts() {
+        let dir = project(&[("a.txt", b"ours\n")]);
+        let changes = apply(dir.path(), &[file("a.txt", "base\n")], &[file("a.txt", "theirs\n")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("a.txt"), SyncAction::Conflict));
+        let merged = read(&dir, "a.txt");
+        assert!(merged.contains("<<<<<<< project\nours\n"), "{}", merged);
+        assert!(merged.contains("theirs\n>>>>>>> template"), "{}", merged);
+    }
+    
+    #[test]
+    fn merges_files_added_on_both_sides() {
+        let dir = project(&[("a.txt", b"ours\n")]);
+        let changes = apply(dir.path(), &[], &[file("a.txt", "theirs\n")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("a.txt"), SyncAction::Conflict));
+    }
+    
+    #[test]
+    fn removes_unchanged_files_dropped_from_the_template() {
+        let dir = project(&[("old.txt", b"old\n")]);
+        let changes = apply(dir.path(), &[file("old.txt", "old\n")], &[]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("old.txt"), SyncAction::Removed));
+        assert!(!dir.path().join("old.txt").exists());
+    }
+    
+    #[test]
+    fn skips_files_deleted_in_the_project() {
+        let dir = project(&[]);
+        let changes = apply(dir.path(), &[file("a.txt", "one\n")], &[file("a.txt", "two\n")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("a.txt"), SyncAction::Skipped));
+        assert!(!dir.path().join("a.txt").exists());
+    }
+    
+    #[test]
+    fn skips_changed_files_dropped_from_the_template() {
+        let dir = project(&[("old.txt", b"edited\n")]);
+        let changes = apply(dir.path(), &[file("old.txt", "old\n")], &[]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("old.txt"), SyncAction::Skipped));
+        assert_eq!(read(&dir, "old.txt"), "edited\n");
+    }
+    
+    #[test]
+    fn ignores_files_already_matching_or_gone_on_both_sides() {
+        let dir = project(&[("same.txt", b"new\n")]);
+        let base = [file("same.txt", "old\n"), file("gone.txt", "old\n"), file("kept.txt", "kept\n")];
+        let theirs = [file("same.txt", "new\n"), file("kept.txt", "kept\n")];
+        let changes = apply(dir.path(), &base, &theirs).unwrap();
+        
+        assert!(changes.is_empty(), "{:?}", changes);
+    }
+    
+    #[test]
+    fn saves_the_template_version_of_conflicting_binary_files() {
+        let dir = project(&[("logo.png", b"\0ours")]);
+        let changes = apply(dir.path(), &[binary("logo.png", b"\0base")], &[binary("logo.png", b"\0theirs")]).unwrap();
+        
+        assert_eq!(only_change(&changes), (Path::new("logo.png"), SyncAction::Conflict));
+        assert_eq!(fs::read(dir.path().join("logo.png")).unwrap(), b"\0ours");
+        assert_eq!(fs::read(dir.path().join("logo.png.template")).unwrap(), b"\0theirs");
+    }
+}
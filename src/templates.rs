@@ -1,655 +1,955 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use flate2::read::GzDecoder;
+use include_dir::{include_dir, Dir};
 use serde::{Deserialize, Serialize};
 
 use crate::config::get_config_dir;
+use crate::fingerprint;
+use crate::git;
+use crate::languages::{self, Language};
+use crate::manifest::{BuildRecipe, Hooks, ProjectManifest, RenderRules, TemplateManifest, VariableKind, VariableSpec, PROJECT_MANIFEST_FILE, TEMPLATE_MANIFEST_FILE};
+use crate::project::Project;
+use crate::prompt::Prompter;
+use crate::render::{self, Value, Variables};
 
-#[derive(Debug, Clone)]
-pub enum TemplateType {
-    Rust,
-    Python,
-    Node,
-    Go,
-    Bash,
-    Zsh,
-    Bun,
-    Custom(String),
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomTemplate {
+    pub name: String,
+    pub path: PathBuf,
+    pub description: Option<String>,
+    /// Set when the template is a clone of a git repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<GitOrigin>,
+    /// Namespace of the pack the template was added with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack: Option<String>,
 }
 
-impl From<&str> for TemplateType {
-    fn from(s: &str) -> Self {
-        match s {
-            "rust" => TemplateType::Rust,
-            "python" => TemplateType::Python,
-            "node" => TemplateType::Node,
-            "go" => TemplateType::Go,
-            "bash" => TemplateType::Bash,
-            "zsh" => TemplateType::Zsh,
-            "bun" => TemplateType::Bun,
-            _ => TemplateType::Custom(s.to_string()),
-        }
-    }
+/// Where a template cloned from git comes from and what it is pinned to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitOrigin {
+    pub url: String,
+    /// Branch, tag or commit to follow (the default branch when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Commit currently checked out
+    pub commit: String,
+}
+
+/// Where a template is defined, from highest to lowest precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateScope {
+    /// A directory under `.murex-templates` in the current directory or a parent
+    Project,
+    /// Registered with `template add`
+    User,
+    /// Shipped with murex
+    Builtin,
 }
 
-impl ToString for TemplateType {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for TemplateScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TemplateType::Rust => "rust".to_string(),
-            TemplateType::Python => "python".to_string(),
-            TemplateType::Node => "node".to_string(),
-            TemplateType::Go => "go".to_string(),
-            TemplateType::Bash => "bash".to_string(),
-            TemplateType::Zsh => "zsh".to_string(),
-            TemplateType::Bun => "bun".to_string(),
-            TemplateType::Custom(name) => name.clone(),
+            TemplateScope::Project => write!(f, "project"),
+            TemplateScope::User => write!(f, "user"),
+            TemplateScope::Builtin => write!(f, "built-in"),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CustomTemplate {
+/// Summary of a template for listings
+#[derive(Debug, Serialize)]
+pub struct TemplateInfo {
     pub name: String,
-    pub path: PathBuf,
+    pub scope: TemplateScope,
+    /// Set when a template of the same name in a higher-precedence scope is used instead
+    pub shadowed_by: Option<TemplateScope>,
+    pub path: Option<PathBuf>,
     pub description: Option<String>,
+    pub origin: Option<GitOrigin>,
+    pub pack: Option<String>,
+}
+
+/// Everything `template show` reports about a template
+#[derive(Debug, Serialize)]
+pub struct TemplateDetails {
+    #[serde(flatten)]
+    pub info: TemplateInfo,
+    /// Tools needed on PATH to build projects made from the template
+    pub requires: Vec<String>,
+    pub build: BuildRecipe,
+    pub hooks: Hooks,
+    pub variables: Vec<VariableSpec>,
+    /// Files a new project starts with, relative to the project root
+    pub files: Vec<TemplateFile>,
+}
+
+/// What `template check` found out about a template
+#[derive(Debug, Default)]
+pub struct TemplateCheck {
+    /// Everything wrong with the template, in the order it was found
+    pub problems: Vec<String>,
+    /// Values the template was rendered with: defaults, or a placeholder
+    pub variables: Variables,
+    /// Files the template rendered to, if rendering worked
+    pub files: Option<Vec<RenderedFile>>,
+    pub build: BuildRecipe,
 }
 
+/// A file produced by rendering a template, not yet written anywhere
+#[derive(Debug)]
+pub struct RenderedFile {
+    /// Path relative to the project directory
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+    /// Permissions copied from the template file, if it came from one
+    pub permissions: Option<fs::Permissions>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Directory, looked for in the current directory and its parents, whose
+/// subdirectories are project-local templates
+const LOCAL_TEMPLATES_DIR: &str = ".murex-templates";
+
+/// Templates are looked up in the current project first, then the user's
+/// registered templates, then the built-in ones.
 pub struct TemplateManager {
+    local_templates: HashMap<String, CustomTemplate>,
     custom_templates: HashMap<String, CustomTemplate>,
+    /// Names resolved in a fixed scope rather than by precedence
+    pinned: HashMap<String, TemplateScope>,
 }
 
 impl TemplateManager {
     pub fn new() -> Result<Self> {
         let mut manager = Self {
+            local_templates: HashMap::new(),
             custom_templates: HashMap::new(),
+            pinned: HashMap::new(),
         };
         
+        manager.load_local_templates()?;
         manager.load_custom_templates()?;
         Ok(manager)
     }
     
-    pub fn list_templates(&self) -> Result<Vec<String>> {
-        let mut templates = vec![
-            "rust".to_string(),
-            "python".to_string(),
-            "node".to_string(),
-            "go".to_string(),
-            "bash".to_string(),
-            "zsh".to_string(),
-            "bun".to_string(),
-        ];
+    /// A manager that resolves a project's template the way it was resolved
+    /// when the project was created, wherever murex is run from
+    pub fn for_project(project_dir: &Path) -> Result<Self> {
+        let mut manager = Self::new()?;
         
-        for name in self.custom_templates.keys() {
-            templates.push(name.clone());
+        if let Some(manifest) = ProjectManifest::load(project_dir)? {
+            let info = manifest.project;
+            if let Some(scope) = info.template_scope {
+                manager.pin_template(&info.template, scope, info.template_path.as_deref())?;
+            }
         }
         
-        templates.sort();
-        Ok(templates)
+        Ok(manager)
     }
     
-    pub fn create_project_from_template(&self, template: &str, project_path: &Path, project_name: &str) -> Result<()> {
-        fs::create_dir_all(project_path)?;
-        
-        match template {
-            "rust" => self.create_rust_project(project_path, project_name),
-            "python" => self.create_python_project(project_path, project_name),
-            "node" => self.create_node_project(project_path, project_name),
-            "go" => self.create_go_project(project_path, project_name),
-            "bash" => self.create_bash_project(project_path, project_name),
-            "zsh" => self.create_zsh_project(project_path, project_name),
-            "bun" => self.create_bun_project(project_path, project_name),
-            _ => {
-                if let Some(custom_template) = self.custom_templates.get(template) {
-                    self.create_from_custom_template(custom_template, project_path, project_name)
-                } else {
-                    Err(anyhow::anyhow!("Unknown template: {}", template))
-                }
+    /// Resolve `name` in `scope` only; project templates are loaded from `path`
+    fn pin_template(&mut self, name: &str, scope: TemplateScope, path: Option<&Path>) -> Result<()> {
+        match scope {
+            TemplateScope::Project => {
+                let path = path
+                    .filter(|path| path.is_dir())
+                    .ok_or_else(|| anyhow::anyhow!("The project template '{}' the project was created from no longer exists", name))?;
+                self.local_templates.insert(name.to_string(), CustomTemplate {
+                    name: name.to_string(),
+                    path: path.to_path_buf(),
+                    description: None,
+                    origin: None,
+                    pack: None,
+                });
+            }
+            TemplateScope::User if !self.custom_templates.contains_key(name) => {
+                return Err(anyhow::anyhow!("The user template '{}' the project was created from is no longer registered", name));
             }
+            TemplateScope::Builtin if languages::get(name).is_none() => {
+                return Err(anyhow::anyhow!("Unknown built-in template: {}", name));
+            }
+            _ => {}
         }
+        
+        self.pinned.insert(name.to_string(), scope);
+        Ok(())
     }
     
-    fn create_rust_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create Cargo.toml
-        let cargo_toml = format!(r#"[package]
-name = "{}"
-version = "0.1.0"
-edition = "2021"
-
-[dependencies]
-clap = {{ version = "4.0", features = ["derive"] }}
-anyhow = "1.0"
-"#, project_name);
-        
-        fs::write(project_path.join("Cargo.toml"), cargo_toml)?;
-        
-        // Create src directory and main.rs
-        let src_dir = project_path.join("src");
-        fs::create_dir_all(&src_dir)?;
+    /// Names of every available template, sorted and without duplicates
+    pub fn list_templates(&self) -> Result<Vec<String>> {
+        let mut templates: Vec<String> = languages::all()
+            .iter()
+            .map(|language| language.name().to_string())
+            .chain(self.custom_templates.keys().cloned())
+            .chain(self.local_templates.keys().cloned())
+            .collect();
         
-        let main_rs = format!(r#"use clap::{{Parser, Subcommand}};
-use anyhow::Result;
-
-#[derive(Parser)]
-#[command(name = "{}")]
-#[command(about = "A CLI utility created with Murex")]
-#[command(version = "0.1.0")]
-struct Cli {{
-    #[command(subcommand)]
-    command: Option<Commands>,
-}}
-
-#[derive(Subcommand)]
-enum Commands {{
-    /// Say hello
-    Hello {{
-        /// Name to greet
-        #[arg(short, long)]
-        name: Option<String>,
-    }},
-}}
-
-fn main() -> Result<()> {{
-    let cli = Cli::parse();
-    
-    match cli.command {{
-        Some(Commands::Hello {{ name }}) => {{
-            let name = name.unwrap_or_else(|| "World".to_string());
-            println!("Hello, {{}}!", name);
-        }}
-        None => {{
-            println!("Welcome to {}! Use --help for more information.");
-        }}
-    }}
+        templates.sort();
+        templates.dedup();
+        Ok(templates)
+    }
     
-    Ok(())
-}}
-"#, project_name, project_name);
+    /// Describe every template in every scope, sorted by name then precedence
+    ///
+    /// Templates hidden by one of the same name in a higher-precedence scope
+    /// are included with `shadowed_by` set.
+    pub fn template_infos(&self) -> Result<Vec<TemplateInfo>> {
+        let mut infos: Vec<TemplateInfo> = languages::all()
+            .iter()
+            .map(|language| builtin_info(*language))
+            .collect();
         
-        fs::write(src_dir.join("main.rs"), main_rs)?;
+        for template in self.custom_templates.values() {
+            infos.push(self.custom_info(template, TemplateScope::User));
+        }
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-cargo build --release
-```
-
-## Usage
-
-```bash
-cargo run -- hello --name "Your Name"
-```
-"#, project_name);
+        for template in self.local_templates.values() {
+            infos.push(self.custom_info(template, TemplateScope::Project));
+        }
         
-        fs::write(project_path.join("README.md"), readme)?;
+        infos.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        for i in 1..infos.len() {
+            if infos[i].name == infos[i - 1].name {
+                infos[i].shadowed_by = infos[i - 1].shadowed_by.or(Some(infos[i - 1].scope));
+            }
+        }
         
-        Ok(())
+        Ok(infos)
     }
     
-    fn create_python_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create main.py
-        let main_py = format!(r#"#!/usr/bin/env python3
-"""
-{} - A CLI utility created with Murex
-"""
-
-import argparse
-import sys
-
-def main():
-    parser = argparse.ArgumentParser(description='A CLI utility created with Murex')
-    parser.add_argument('--version', action='version', version='%(prog)s 0.1.0')
+    /// The scope a template name resolves to
+    pub fn template_scope(&self, name: &str) -> Option<TemplateScope> {
+        if let Some(scope) = self.pinned.get(name) {
+            Some(*scope)
+        } else if self.local_templates.contains_key(name) {
+            Some(TemplateScope::Project)
+        } else if self.custom_templates.contains_key(name) {
+            Some(TemplateScope::User)
+        } else if languages::get(name).is_some() {
+            Some(TemplateScope::Builtin)
+        } else {
+            None
+        }
+    }
     
-    subparsers = parser.add_subparsers(dest='command', help='Available commands')
+    /// The project-local or user template a name resolves to, if it isn't built-in
+    fn custom_template(&self, name: &str) -> Option<&CustomTemplate> {
+        match self.template_scope(name)? {
+            TemplateScope::Project => self.local_templates.get(name),
+            TemplateScope::User => self.custom_templates.get(name),
+            TemplateScope::Builtin => None,
+        }
+    }
     
-    # Hello command
-    hello_parser = subparsers.add_parser('hello', help='Say hello')
-    hello_parser.add_argument('-n', '--name', default='World', help='Name to greet')
+    /// Directory of the project-local template a name resolves to
+    pub fn local_template_path(&self, name: &str) -> Option<PathBuf> {
+        match self.template_scope(name)? {
+            TemplateScope::Project => self.local_templates.get(name).map(|template| template.path.clone()),
+            _ => None,
+        }
+    }
     
-    args = parser.parse_args()
+    fn custom_info(&self, template: &CustomTemplate, scope: TemplateScope) -> TemplateInfo {
+        // A broken manifest shouldn't stop the template from being listed
+        let description = template.description.clone().or_else(|| {
+            TemplateManifest::load(&template.path)
+                .ok()
+                .flatten()
+                .and_then(|manifest| manifest.description)
+        });
+        
+        TemplateInfo {
+            name: template.name.clone(),
+            scope,
+            shadowed_by: None,
+            path: Some(template.path.clone()),
+            description,
+            origin: template.origin.clone(),
+            pack: template.pack.clone(),
+        }
+    }
     
-    if args.command == 'hello':
-        print(f"Hello, {{args.name}}!")
-    else:
-        print(f"Welcome to {}! Use --help for more information.")
-
-if __name__ == '__main__':
-    main()
-"#, project_name, project_name);
+    /// Describe a template in full, including the files a project starts with
+    pub fn template_details(&self, name: &str) -> Result<TemplateDetails> {
+        let info = match (self.template_scope(name), self.custom_template(name), languages::get(name)) {
+            (Some(scope), Some(template), _) => self.custom_info(template, scope),
+            (_, None, Some(language)) => builtin_info(language),
+            _ => return Err(anyhow::anyhow!("Template '{}' not found", name)),
+        };
         
-        fs::write(project_path.join("main.py"), main_py)?;
+        let source = self.source(name)?;
+        let manifest = source.manifest()?.unwrap_or_default();
         
-        // Create requirements.txt
-        let requirements = "# Add your dependencies here\n";
-        fs::write(project_path.join("requirements.txt"), requirements)?;
+        let mut requires = manifest.build.requires.clone();
+        if let Some(language) = manifest.build.language.as_deref().and_then(languages::get) {
+            requires.extend(language.required_tools().iter().map(|tool| tool.to_string()));
+        }
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-pip install -r requirements.txt
-```
-
-## Usage
-
-```bash
-python main.py hello --name "Your Name"
-```
-"#, project_name);
+        let files = source.files()?
+            .into_iter()
+            .map(|file| TemplateFile {
+                size: file.contents.len() as u64,
+                path: file.path,
+            })
+            .collect();
         
-        fs::write(project_path.join("README.md"), readme)?;
+        Ok(TemplateDetails {
+            info,
+            requires,
+            build: manifest.build,
+            hooks: manifest.hooks,
+            variables: manifest.variables,
+            files,
+        })
+    }
+    
+    /// Work out the value of every variable a template declares
+    ///
+    /// Values come from `overrides` (`--var key=value`) first, then from
+    /// prompting, falling back to the declared defaults. `PROJECT_NAME` is
+    /// always set.
+    pub fn resolve_variables(&self, template: &str, project_name: &str, overrides: &[(String, String)], prompter: &Prompter) -> Result<Variables> {
+        let specs = self.template_manifest(template)?
+            .map(|manifest| manifest.variables)
+            .unwrap_or_default();
         
-        Ok(())
+        for (key, _) in overrides {
+            if !specs.iter().any(|spec| &spec.name == key) {
+                return Err(anyhow::anyhow!("Template '{}' has no variable named '{}'", template, key));
+            }
+        }
+        
+        let mut variables = Variables::new();
+        variables.insert("PROJECT_NAME".to_string(), Value::String(project_name.to_string()));
+        
+        for spec in &specs {
+            if let Some((_, raw)) = overrides.iter().rev().find(|(key, _)| key == &spec.name) {
+                variables.insert(spec.name.clone(), spec.parse_value(raw)?);
+            }
+        }
+        
+        self.complete_variables(template, variables, prompter)
     }
     
-    fn create_node_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create package.json
-        let package_json = format!(r#"{{
-  "name": "{}",
-  "version": "0.1.0",
-  "description": "A CLI utility created with Murex",
-  "main": "index.js",
-  "bin": {{
-    "{}": "./index.js"
-  }},
-  "scripts": {{
-    "start": "node index.js"
-  }},
-  "dependencies": {{
-    "commander": "^9.0.0"
-  }},
-  "keywords": ["cli"],
-  "author": "",
-  "license": "MIT"
-}}
-"#, project_name, project_name);
-        
-        fs::write(project_path.join("package.json"), package_json)?;
-        
-        // Create index.js
-        let index_js = format!(r#"#!/usr/bin/env node
-
-const {{ Command }} = require('commander');
-const program = new Command();
-
-program
-  .name('{}')
-  .description('A CLI utility created with Murex')
-  .version('0.1.0');
-
-program
-  .command('hello')
-  .description('Say hello')
-  .option('-n, --name <name>', 'Name to greet', 'World')
-  .action((options) => {{
-    console.log(`Hello, ${{options.name}}!`);
-  }});
-
-if (process.argv.length === 2) {{
-  console.log('Welcome to {}! Use --help for more information.');
-}} else {{
-  program.parse();
-}}
-"#, project_name, project_name);
-        
-        fs::write(project_path.join("index.js"), index_js)?;
-        
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-npm install
-```
-
-## Usage
-
-```bash
-node index.js hello --name "Your Name"
-```
-"#, project_name);
-        
-        fs::write(project_path.join("README.md"), readme)?;
+    /// Fill in every declared variable missing from `variables`, by prompting or from its default
+    pub fn complete_variables(&self, template: &str, mut variables: Variables, prompter: &Prompter) -> Result<Variables> {
+        let specs = self.template_manifest(template)?
+            .map(|manifest| manifest.variables)
+            .unwrap_or_default();
         
-        Ok(())
+        for spec in &specs {
+            if variables.contains_key(&spec.name) {
+                continue;
+            }
+            
+            let default = spec.default_value(&variables)?;
+            let value = match (spec.kind, &default) {
+                (VariableKind::Bool, Value::Bool(default)) => Value::Bool(prompter.toggle(spec.prompt_text(), *default)?),
+                _ => spec.parse_value(&prompter.input(spec.prompt_text(), default.to_string(), true)?)?,
+            };
+            variables.insert(spec.name.clone(), value);
+        }
+        
+        Ok(variables)
     }
     
-    fn create_go_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create go.mod
-        let go_mod = format!(r#"module {}
-
-go 1.19
-
-require github.com/spf13/cobra v1.6.1
-
-require (
-	github.com/inconshreveable/mousetrap v1.0.1 // indirect
-	github.com/spf13/pflag v1.0.5 // indirect
-)
-"#, project_name);
+    /// Look for problems in a template without creating a project
+    ///
+    /// Checks the manifest, that every variable used in file names, file
+    /// contents and build commands is declared, and that the template renders
+    /// for `project_name` with each variable's default. Problems are
+    /// collected rather than returned as errors so they can all be reported.
+    pub fn check_template(&self, name: &str, project_name: &str) -> Result<TemplateCheck> {
+        let source = self.source(name)?;
+        let mut check = TemplateCheck::default();
+        
+        // Rendering reads the manifest again, so it is only attempted when the manifest is usable
+        let (manifest, mut renderable) = match source.manifest() {
+            Ok(Some(manifest)) => (manifest, true),
+            Ok(None) => {
+                check.problems.push(format!("No {} describing how to build projects", TEMPLATE_MANIFEST_FILE));
+                (TemplateManifest::default(), true)
+            }
+            Err(e) => {
+                check.problems.push(e.to_string().trim_end().to_string());
+                (TemplateManifest::default(), false)
+            }
+        };
         
-        fs::write(project_path.join("go.mod"), go_mod)?;
+        if let Some(language) = &manifest.build.language {
+            if languages::get(language).is_none() {
+                check.problems.push(format!("Unknown build language '{}'", language));
+            }
+        }
         
-        // Create main.go
-        let main_go = format!(r#"package main
-
-import (
-	"fmt"
-	"os"
-
-	"github.com/spf13/cobra"
-)
-
-var rootCmd = &cobra.Command{{
-	Use:   "{}",
-	Short: "A CLI utility created with Murex",
-	Long:  "A CLI utility created with Murex",
-	Run: func(cmd *cobra.Command, args []string) {{
-		fmt.Println("Welcome to {}! Use --help for more information.")
-	}},
-}}
-
-var helloCmd = &cobra.Command{{
-	Use:   "hello",
-	Short: "Say hello",
-	Run: func(cmd *cobra.Command, args []string) {{
-		name, _ := cmd.Flags().GetString("name")
-		fmt.Printf("Hello, %s!\\n", name)
-	}},
-}}
-
-func init() {{
-	helloCmd.Flags().StringP("name", "n", "World", "Name to greet")
-	rootCmd.AddCommand(helloCmd)
-}}
-
-func main() {{
-	if err := rootCmd.Execute(); err != nil {{
-		fmt.Println(err)
-		os.Exit(1)
-	}}
-}}
-"#, project_name, project_name);
+        let filter = match manifest.render.compile() {
+            Ok(filter) => filter,
+            Err(e) => {
+                check.problems.push(e.to_string());
+                renderable = false;
+                RenderRules::default().compile()?
+            }
+        };
         
-        fs::write(project_path.join("main.go"), main_go)?;
+        // Declared variables, with the value each gets when rendering
+        check.variables.insert("PROJECT_NAME".to_string(), Value::String(project_name.to_string()));
+        for spec in &manifest.variables {
+            if !render::is_identifier(&spec.name) {
+                check.problems.push(format!("Variable name '{}' is not a valid identifier", spec.name));
+            }
+            if check.variables.contains_key(&spec.name) {
+                check.problems.push(format!("Variable '{}' is declared more than once or is reserved", spec.name));
+                continue;
+            }
+            
+            let value = match spec.default_value(&check.variables) {
+                Ok(Value::String(value)) if value.is_empty() => Value::String(spec.name.clone()),
+                Ok(value) => value,
+                Err(e) => {
+                    check.problems.push(e.to_string());
+                    Value::String(spec.name.clone())
+                }
+            };
+            check.variables.insert(spec.name.clone(), value);
+        }
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-go build -o {}
-```
-
-## Usage
-
-```bash
-./{} hello --name "Your Name"
-```
-"#, project_name, project_name, project_name);
+        // Every place a variable can be used, by where it is used
+        let mut texts: Vec<(String, String)> = Vec::new();
+        for (i, command) in manifest.build.commands.iter().enumerate() {
+            texts.push((format!("build command {}", i + 1), command.clone()));
+        }
+        if let Some(entrypoint) = &manifest.build.entrypoint {
+            texts.push(("build entrypoint".to_string(), entrypoint.clone()));
+        }
+        for (i, command) in manifest.hooks.post_create.iter().enumerate() {
+            texts.push((format!("post-create hook {}", i + 1), command.clone()));
+        }
+        for spec in &manifest.variables {
+            if let Some(toml::Value::String(default)) = &spec.default {
+                texts.push((format!("default of '{}'", spec.name), default.clone()));
+            }
+        }
         
-        fs::write(project_path.join("README.md"), readme)?;
+        let files = source.files()?;
+        for file in &files {
+            let location = file.path.display().to_string();
+            texts.push((format!("name of {}", location), file.path.to_string_lossy().to_string()));
+            
+            // Rendering filters on the rendered path; a name that doesn't render is reported above
+            let path = render_path(&file.path, &check.variables).unwrap_or_else(|_| file.path.clone());
+            if let Some(content) = as_text(&file.contents).filter(|_| filter.should_render(&path)) {
+                texts.push((location, content.to_string()));
+            }
+        }
+        
+        let mut undeclared: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (location, text) in texts {
+            match render::Template::parse(&text) {
+                Ok(template) => {
+                    for variable in template.variables_used() {
+                        if !check.variables.contains_key(&variable) {
+                            undeclared.entry(variable).or_default().push(location.clone());
+                        }
+                    }
+                }
+                Err(e) => check.problems.push(format!("{}: {}", location, e)),
+            }
+        }
+        for (variable, locations) in undeclared {
+            check.problems.push(format!(
+                "Variable '{}' is used but not declared (in {}); declare it or escape it as \\{{{{",
+                variable,
+                locations.join(", ")
+            ));
+        }
+        
+        if renderable {
+            match source.render(&check.variables) {
+                Ok(files) => check.files = Some(files),
+                Err(e) => check.problems.push(format!("Rendering failed: {}", e)),
+            }
+        }
+        match manifest.build.for_project(&check.variables) {
+            Ok(build) => check.build = build,
+            Err(e) => check.problems.push(format!("Build recipe: {}", e)),
+        }
+        
+        Ok(check)
+    }
+    
+    /// The post-create hooks a template declares, rendered for a project
+    pub fn post_create_hooks(&self, template: &str, variables: &Variables) -> Result<Hooks> {
+        match self.template_manifest(template)? {
+            Some(manifest) => manifest.hooks.for_project(variables),
+            None => Ok(Hooks::default()),
+        }
+    }
+    
+    /// Render a template into memory, returning the files a new project starts with
+    pub fn render_template(&self, template: &str, variables: &Variables) -> Result<Vec<RenderedFile>> {
+        self.source(template)?.render(variables)
+    }
+    
+    /// Identifies the current contents of a template
+    ///
+    /// Git templates use the checked out commit, directory templates a
+    /// fingerprint of their files and built-in templates the murex version.
+    pub fn template_version(&self, template: &str) -> Result<String> {
+        match (self.custom_template(template), languages::get(template)) {
+            (Some(custom_template), _) => match &custom_template.origin {
+                Some(origin) => Ok(origin.commit.clone()),
+                None => fingerprint::compute(&custom_template.path, &[]),
+            },
+            (None, Some(_)) => Ok(format!("murex-{}", env!("CARGO_PKG_VERSION"))),
+            (None, None) => Err(anyhow::anyhow!("Unknown template: {}", template)),
+        }
+    }
+    
+    /// Keep a copy of a template's current files so it can be rendered at this version later
+    ///
+    /// Git templates keep their own history, so only directory and built-in
+    /// templates are copied.
+    pub fn snapshot_template(&self, template: &str, version: &str) -> Result<()> {
+        if self.custom_template(template).is_some_and(|t| t.origin.is_some()) {
+            return Ok(());
+        }
+        
+        let dest = snapshot_dir(template, version)?;
+        if dest.exists() {
+            return Ok(());
+        }
+        
+        let scratch = dest.with_extension("partial");
+        let _ = fs::remove_dir_all(&scratch);
+        self.source(template)?.export(&scratch)?;
+        fs::rename(&scratch, &dest)?;
         
         Ok(())
     }
     
-    fn create_bash_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create main.sh
-        let main_sh = format!(r#"#!/bin/bash
-
-# {}
-# A CLI utility created with Murex
-
-# Hello command
-hello() {{
-    name=${{1:-World}}
-    echo "Hello, $name!"
-}}
-
-# Main function
-main() {{
-    case $1 in
-        hello)
-            hello $2
-            ;;
-        --help|-h)
-            echo "Usage: {} [command] [options]"
-            echo "Commands:"
-            echo "  hello [name]  Say hello to someone"
-            echo "  --help        Show this help message"
-            ;;
-        *)
-            echo "Welcome to {}! Use --help for more information."
-            ;;
-    esac
-}}
-
-main "$@"
-"#, project_name, project_name, project_name);
+    /// Render a template as it was at `version`
+    pub fn render_template_at(&self, template: &str, version: &str, variables: &Variables) -> Result<Vec<RenderedFile>> {
+        if self.template_version(template)? == version {
+            return self.render_template(template, variables);
+        }
         
-        fs::write(project_path.join("main.sh"), main_sh)?;
+        let unavailable = || anyhow::anyhow!("Version {} of template '{}' is no longer available", version, template);
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-chmod +x main.sh
-```
-
-## Usage
-
-```bash
-./main.sh hello "Your Name"
-```
-"#, project_name);
+        if let Some(custom_template) = self.custom_template(template).filter(|t| t.origin.is_some()) {
+            // Check the old commit out into a throwaway clone of the template repository
+            let scratch = tempfile::tempdir()?;
+            let checkout = scratch.path().join(template);
+            git::clone(&custom_template.path.to_string_lossy(), &checkout)?;
+            git::checkout(&checkout, version).map_err(|_| unavailable())?;
+            return TemplateSource::Directory(checkout).render(variables);
+        }
         
-        fs::write(project_path.join("README.md"), readme)?;
+        let snapshot = snapshot_dir(template, version)?;
+        if !snapshot.is_dir() {
+            return Err(unavailable());
+        }
+        TemplateSource::Directory(snapshot).render(variables)
+    }
+    
+    /// Copy a built-in template's files out to `dest` so it can be customized
+    ///
+    /// This always copies the built-in template, even when a user or
+    /// project template of the same name shadows it.
+    pub fn eject_template(&self, name: &str, dest: &Path) -> Result<()> {
+        if dest.exists() {
+            return Err(anyhow::anyhow!("Destination already exists: {}", dest.display()));
+        }
         
-        Ok(())
+        builtin_source(name)?.export(dest)
     }
     
-    fn create_zsh_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create main.zsh
-        let main_zsh = format!(r#"#!/bin/zsh
-
-# {}
-# A CLI utility created with Murex
-
-# Hello command
-hello() {{
-    name=${{1:-World}}
-    echo "Hello, $name!"
-}}
-
-# Main function
-main() {{
-    case $1 in
-        hello)
-            hello $2
-            ;;
-        --help|-h)
-            echo "Usage: {} [command] [options]"
-            echo "Commands:"
-            echo "  hello [name]  Say hello to someone"
-            echo "  --help        Show this help message"
-            ;;
-        *)
-            echo "Welcome to {}! Use --help for more information."
-            ;;
-    esac
-}}
-
-main "$@"
-"#, project_name, project_name, project_name);
+    /// Where the template a name resolves to gets its files from
+    fn source(&self, name: &str) -> Result<TemplateSource> {
+        match self.custom_template(name) {
+            Some(template) => Ok(TemplateSource::Directory(template.path.clone())),
+            None if languages::get(name).is_some() => builtin_source(name),
+            None => Err(anyhow::anyhow!("Unknown template: {}", name)),
+        }
+    }
+    
+    pub fn add_template(&mut self, name: String, path: PathBuf, description: Option<String>) -> Result<()> {
+        if !path.exists() || !path.is_dir() {
+            return Err(anyhow::anyhow!("Template path must be an existing directory"));
+        }
         
-        fs::write(project_path.join("main.zsh"), main_zsh)?;
+        self.insert_template(CustomTemplate {
+            name,
+            path,
+            description,
+            origin: None,
+            pack: None,
+        })
+    }
+    
+    /// Clone a git repository into the config directory and register it as a template
+    ///
+    /// The clone is pinned to `reference` (a branch, tag or commit; the
+    /// default branch when `None`) and only moves on `update_template`.
+    pub fn add_git_template(&mut self, name: String, url: &str, reference: Option<String>, description: Option<String>) -> Result<GitOrigin> {
+        // The name becomes a directory under repos/, so check it before touching the filesystem
+        validate_template_name(&name)?;
+        if self.custom_templates.contains_key(&name) {
+            return Err(anyhow::anyhow!("Template '{}' already exists", name));
+        }
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-chmod +x main.zsh
-```
-
-## Usage
-
-```bash
-./main.zsh hello "Your Name"
-```
-"#, project_name);
+        // Store local repositories by absolute path so updates work from anywhere
+        let url = match Path::new(url).canonicalize() {
+            Ok(path) => path.to_string_lossy().to_string(),
+            Err(_) => url.to_string(),
+        };
         
-        fs::write(project_path.join("README.md"), readme)?;
+        let dest = get_config_dir()?.join("repos").join(&name);
+        if dest.exists() {
+            return Err(anyhow::anyhow!("Destination already exists: {}", dest.display()));
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
         
-        Ok(())
+        let result = git::clone(&url, &dest)
+            .and_then(|_| git::resolve(&dest, reference.as_deref()))
+            .and_then(|commit| {
+                git::checkout(&dest, &commit)?;
+                
+                let origin = GitOrigin { url, reference, commit };
+                self.insert_template(CustomTemplate {
+                    name,
+                    path: dest.clone(),
+                    description,
+                    origin: Some(origin.clone()),
+                    pack: None,
+                })?;
+                Ok(origin)
+            });
+        
+        // Don't leave a half-cloned repository behind
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&dest);
+        }
+        
+        result
     }
     
-    fn create_bun_project(&self, project_path: &Path, project_name: &str) -> Result<()> {
-        // Create bun.js
-        let bun_js = format!(r#"#!/usr/bin/env bun
-
-import {{ Command }} from 'bun';
-
-const program = new Command();
-
-program
-  .name('{}')
-  .description('A CLI utility created with Murex')
-  .version('0.1.0');
-
-program
-  .command('hello')
-  .description('Say hello')
-  .option('-n, --name <n>', 'Name to greet', 'World')
-  .action((options) => {{
-    console.log(`Hello, ${{options.name}}!`);
-  }});
-
-if (process.argv.length === 2) {{
-  console.log('Welcome to {}! Use --help for more information.');
-}} else {{
-  program.parse();
-}}
-"#, project_name, project_name);
+    /// Fetch a git template and check out its reference again, or a new one
+    ///
+    /// Returns the previously and newly checked out commits.
+    pub fn update_template(&mut self, name: &str, reference: Option<String>) -> Result<(String, String)> {
+        let template = self.custom_templates
+            .get_mut(name)
+            .ok_or_else(|| anyhow::anyhow!("Template '{}' not found", name))?;
+        let origin = template.origin
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Template '{}' was not added from a git repository", name))?;
         
-        fs::write(project_path.join("bun.js"), bun_js)?;
+        git::fetch(&template.path)?;
         
-        // Create README
-        let readme = format!(r#"# {}
-
-A CLI utility created with Murex.
-
-## Installation
-
-```bash
-bun install
-```
-
-## Usage
-
-```bash
-bun run bun.js hello --name "Your Name"
-```
-"#, project_name);
+        let reference = reference.or_else(|| origin.reference.clone());
+        let commit = git::resolve(&template.path, reference.as_deref())?;
+        git::checkout(&template.path, &commit)?;
         
-        fs::write(project_path.join("README.md"), readme)?;
+        // The new revision has to be usable, otherwise go back to the old one
+        if let Err(e) = TemplateManifest::load(&template.path) {
+            git::checkout(&template.path, &origin.commit)?;
+            return Err(e);
+        }
         
-        Ok(())
+        origin.reference = reference;
+        let previous = std::mem::replace(&mut origin.commit, commit.clone());
+        self.save_custom_templates()?;
+        
+        Ok((previous, commit))
     }
     
-    fn create_from_custom_template(&self, template: &CustomTemplate, project_path: &Path, project_name: &str) -> Result<()> {
-        // Copy template directory to project path
-        self.copy_dir_recursive(&template.path, project_path)?;
+    /// Register every template in a pack directory or `.tar.gz` archive
+    ///
+    /// Each directory holding a `murex-template.toml` becomes a template named
+    /// `<namespace>/<path within the pack>`. Archives are unpacked into the
+    /// config directory, directories are used in place. The namespace
+    /// defaults to the pack's file name. Returns the names added.
+    pub fn add_pack(&mut self, source: &Path, namespace: Option<String>) -> Result<Vec<String>> {
+        let archive = is_archive(source);
+        if !archive && !source.is_dir() {
+            return Err(anyhow::anyhow!("Pack must be a directory or a .tar.gz archive: {}", source.display()));
+        }
         
-        // Replace placeholders in files
-        self.replace_placeholders_in_directory(project_path, project_name)?;
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => pack_namespace(source)?,
+        };
+        if namespace.is_empty() || namespace.starts_with('.') || namespace.contains(INVALID_NAME_CHARS) {
+            return Err(anyhow::anyhow!("Invalid pack namespace: '{}'", namespace));
+        }
+        if self.custom_templates.values().any(|t| t.pack.as_deref() == Some(namespace.as_str())) {
+            return Err(anyhow::anyhow!("Pack '{}' is already added; remove it first with template remove-pack", namespace));
+        }
         
-        Ok(())
+        if !archive {
+            return self.register_pack(&source.canonicalize()?, &namespace);
+        }
+        
+        let dest = get_config_dir()?.join("packs").join(&namespace);
+        if dest.exists() {
+            return Err(anyhow::anyhow!("Destination already exists: {}", dest.display()));
+        }
+        unpack_archive(source, &dest)?;
+        
+        // Archives usually wrap everything in one top-level directory
+        let entries: Vec<PathBuf> = fs::read_dir(&dest)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()?;
+        let root = match entries.as_slice() {
+            [only] if only.is_dir() => only.clone(),
+            _ => dest.clone(),
+        };
+        
+        let result = self.register_pack(&root, &namespace);
+        
+        // Don't keep an unpacked archive nothing refers to
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&dest);
+        }
+        
+        result
     }
     
-    fn copy_dir_recursive(&self, src: &Path, dst: &Path) -> Result<()> {
-        fs::create_dir_all(dst)?;
+    fn register_pack(&mut self, root: &Path, namespace: &str) -> Result<Vec<String>> {
+        let mut dirs = Vec::new();
+        find_template_dirs(root, &mut dirs)?;
+        if dirs.is_empty() {
+            return Err(anyhow::anyhow!("No {} found in {}", TEMPLATE_MANIFEST_FILE, root.display()));
+        }
         
-        for entry in fs::read_dir(src)? {
-            let entry = entry?;
-            let src_path = entry.path();
-            let dst_path = dst.join(entry.file_name());
+        let mut templates = Vec::new();
+        for dir in dirs {
+            let mut name = namespace.to_string();
+            for component in dir.strip_prefix(root)?.iter() {
+                name.push('/');
+                name.push_str(&component.to_string_lossy());
+            }
             
-            if src_path.is_dir() {
-                self.copy_dir_recursive(&src_path, &dst_path)?;
-            } else {
-                fs::copy(&src_path, &dst_path)?;
+            if self.custom_templates.contains_key(&name) {
+                return Err(anyhow::anyhow!("Template '{}' already exists", name));
             }
+            
+            // Check every manifest before registering anything
+            TemplateManifest::load(&dir)?;
+            templates.push(CustomTemplate {
+                name,
+                path: dir,
+                description: None,
+                origin: None,
+                pack: Some(namespace.to_string()),
+            });
         }
         
-        Ok(())
+        let names = templates.iter().map(|template| template.name.clone()).collect();
+        for template in templates {
+            self.custom_templates.insert(template.name.clone(), template);
+        }
+        self.save_custom_templates()?;
+        
+        Ok(names)
     }
     
-    fn replace_placeholders_in_directory(&self, dir: &Path, project_name: &str) -> Result<()> {
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if path.is_dir() {
-                self.replace_placeholders_in_directory(&path, project_name)?;
-            } else if let Some(extension) = path.extension() {
-                // Only process text files
-                if matches!(extension.to_str(), Some("rs") | Some("py") | Some("js") | Some("go") | Some("md") | Some("toml") | Some("json")) {
-                    let content = fs::read_to_string(&path)?;
-                    let updated_content = content.replace("{{PROJECT_NAME}}", project_name);
-                    fs::write(&path, updated_content)?;
+    /// Unregister every template added with a pack, returning their names
+    pub fn remove_pack(&mut self, namespace: &str) -> Result<Vec<String>> {
+        let mut names: Vec<String> = self.custom_templates
+            .values()
+            .filter(|template| template.pack.as_deref() == Some(namespace))
+            .map(|template| template.name.clone())
+            .collect();
+        if names.is_empty() {
+            return Err(anyhow::anyhow!("Pack '{}' not found", namespace));
+        }
+        names.sort();
+        
+        for name in &names {
+            self.custom_templates.remove(name);
+        }
+        self.save_custom_templates()?;
+        
+        // Unpacked archives are owned by murex; pack directories belong to the user
+        let unpacked = get_config_dir()?.join("packs").join(namespace);
+        if unpacked.is_dir() {
+            fs::remove_dir_all(&unpacked)?;
+        }
+        
+        Ok(names)
+    }
+    
+    fn insert_template(&mut self, template: CustomTemplate) -> Result<()> {
+        if template.pack.is_none() {
+            validate_template_name(&template.name)?;
+        }
+        
+        // Fail early on a malformed manifest rather than at build time
+        TemplateManifest::load(&template.path)?;
+        
+        self.custom_templates.insert(template.name.clone(), template);
+        self.save_custom_templates()
+    }
+    
+    /// Get the manifest of a template, if it exists and has one
+    pub fn template_manifest(&self, name: &str) -> Result<Option<TemplateManifest>> {
+        match self.source(name) {
+            Ok(source) => source.manifest(),
+            Err(_) => Ok(None),
+        }
+    }
+    
+    /// Get the build recipe declared by a custom template's manifest
+    pub fn build_recipe(&self, name: &str) -> Result<BuildRecipe> {
+        if self.custom_template(name).is_none() {
+            return Err(anyhow::anyhow!("Unknown template: {}", name));
+        }
+        
+        self.template_manifest(name)?
+            .map(|manifest| manifest.build)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Template '{}' has no {} describing how to build it",
+                name,
+                TEMPLATE_MANIFEST_FILE
+            ))
+    }
+    
+    /// Turn a project into a custom template stored at `dest` and register it
+    ///
+    /// Build artifacts, anything matched by the project's `.gitignore` and
+    /// `binary` (unless it is the language's own main script) are left out.
+    /// Occurrences of the project name become `{{PROJECT_NAME}}` in file
+    /// contents and names, and braces that were already there are escaped
+    /// so they survive rendering.
+    pub fn save_project_as_template(&mut self, project: &Project, name: &str, dest: &Path, binary: Option<&Path>) -> Result<()> {
+        if self.custom_templates.contains_key(name) {
+            return Err(anyhow::anyhow!("Template '{}' already exists", name));
+        }
+        
+        if dest.exists() {
+            return Err(anyhow::anyhow!("Destination already exists: {}", dest.display()));
+        }
+        
+        if !project.path.is_dir() {
+            return Err(anyhow::anyhow!("Project directory does not exist: {}", project.path.display()));
+        }
+        
+        // The entrypoint is build output unless the language runs its main script directly
+        let recipe = project.manifest_recipe()?.unwrap_or_default();
+        let language = recipe.language.as_deref().unwrap_or(&project.template);
+        let source_script = languages::get(language)
+            .filter(|_| recipe.commands.is_empty())
+            .map(|language| project.path.join(language.main_file()));
+        let artifact = binary.filter(|binary| source_script.as_deref() != Some(*binary));
+        
+        let mut always = vec![format!("/{}", PROJECT_MANIFEST_FILE)];
+        if let Some(relative) = artifact.and_then(|binary| binary.strip_prefix(&project.path).ok()) {
+            always.push(format!("/{}", relative.display()));
+        }
+        let ignore = build_ignore(&project.path, ".gitignore", &always)?;
+        
+        let result = copy_as_template(&project.path, dest, &ignore, &project.name)
+            .and_then(|_| {
+                let mut build = recipe;
+                build.commands = build.commands
+                    .iter()
+                    .map(|command| templatize_name(command, &project.name))
+                    .collect();
+                build.entrypoint = build.entrypoint
+                    .map(|entrypoint| templatize_name(&entrypoint, &project.name));
+                if build.commands.is_empty() && build.language.is_none() && languages::get(&project.template).is_some() {
+                    build.language = Some(project.template.clone());
                 }
-            }
+                
+                let manifest = TemplateManifest {
+                    description: Some(format!("Saved from project {}", project.name)),
+                    build,
+                    ..TemplateManifest::default()
+                };
+                manifest.save(dest)
+            })
+            .and_then(|_| self.add_template(name.to_string(), dest.to_path_buf(), None));
+        
+        // Don't leave a half-written template behind
+        if result.is_err() {
+            let _ = fs::remove_dir_all(dest);
         }
         
-        Ok(())
+        result
     }
     
-    pub fn add_template(&mut self, name: String, path: PathBuf) -> Result<()> {
-        if !path.exists() || !path.is_dir() {
-            return Err(anyhow::anyhow!("Template path must be an existing directory"));
+    pub fn remove_template(&mut self, name: &str) -> Result<()> {
+        if let Some(pack) = self.custom_templates.get(name).and_then(|t| t.pack.as_ref()) {
+            return Err(anyhow::anyhow!("'{}' belongs to pack '{}'; remove the pack with template remove-pack {}", name, pack, pack));
         }
         
-        let template = CustomTemplate {
-            name: name.clone(),
-            path,
-            description: None,
+        let template = match self.custom_templates.remove(name) {
+            Some(template) => template,
+            None if self.local_templates.contains_key(name) => {
+                return Err(anyhow::anyhow!("'{}' is a project template; delete its directory to remove it", name));
+            }
+            None if languages::get(name).is_some() => {
+                return Err(anyhow::anyhow!("'{}' is a built-in template and can't be removed", name));
+            }
+            None => return Err(anyhow::anyhow!("Template '{}' not found", name)),
         };
         
-        self.custom_templates.insert(name, template);
         self.save_custom_templates()?;
         
+        // Clones are owned by murex; plain directories belong to the user
+        if template.origin.is_some() && template.path.starts_with(get_config_dir()?.join("repos")) {
+            fs::remove_dir_all(&template.path)?;
+        }
+        
         Ok(())
     }
     
-    pub fn remove_template(&mut self, name: &str) -> Result<()> {
-        if self.custom_templates.remove(name).is_none() {
-            return Err(anyhow::anyhow!("Template '{}' not found", name));
+    /// Pick up templates from the nearest `.murex-templates` directory
+    fn load_local_templates(&mut self) -> Result<()> {
+        let current_dir = match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return Ok(()),
+        };
+        
+        let Some(local_dir) = current_dir
+            .ancestors()
+            .map(|dir| dir.join(LOCAL_TEMPLATES_DIR))
+            .find(|dir| dir.is_dir())
+        else {
+            return Ok(());
+        };
+        
+        for entry in fs::read_dir(&local_dir)? {
+            let path = entry?.path();
+            let name = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) if path.is_dir() && !name.starts_with('.') => name.to_string(),
+                _ => continue,
+            };
+            
+            self.local_templates.insert(name.clone(), CustomTemplate {
+                name,
+                path,
+                description: None,
+                origin: None,
+                pack: None,
+            });
         }
         
-        self.save_custom_templates()?;
         Ok(())
     }
     
@@ -680,3 +980,693 @@ bun run bun.js hello --name "Your Name"
         Ok(())
     }
 }
+
+/// Write rendered files under `root`, creating directories as needed
+pub fn write_rendered(root: &Path, files: &[RenderedFile]) -> Result<()> {
+    fs::create_dir_all(root)?;
+    
+    for file in files {
+        let path = root.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        
+        fs::write(&path, &file.contents)?;
+        if let Some(permissions) = &file.permissions {
+            fs::set_permissions(&path, permissions.clone())?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Where the copy of a directory template at `version` is kept
+fn snapshot_dir(template: &str, version: &str) -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("snapshots").join(template).join(version))
+}
+
+fn builtin_info(language: &dyn Language) -> TemplateInfo {
+    let description = BUILTIN_TEMPLATES
+        .get_dir(language.name())
+        .and_then(|dir| TemplateSource::Embedded(dir).manifest().ok().flatten())
+        .and_then(|manifest| manifest.description);
+    
+    TemplateInfo {
+        name: language.name().to_string(),
+        scope: TemplateScope::Builtin,
+        shadowed_by: None,
+        path: None,
+        description,
+        origin: None,
+        pack: None,
+    }
+}
+
+/// Built-in templates, one directory per language, compiled into murex
+static BUILTIN_TEMPLATES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+fn builtin_source(name: &str) -> Result<TemplateSource> {
+    if languages::get(name).is_none() {
+        return Err(anyhow::anyhow!("'{}' is not a built-in template", name));
+    }
+    
+    BUILTIN_TEMPLATES
+        .get_dir(name)
+        .map(TemplateSource::Embedded)
+        .ok_or_else(|| anyhow::anyhow!("Built-in template '{}' has no files", name))
+}
+
+/// Suffix dropped from rendered file names
+///
+/// It lets a template ship files such as `Cargo.toml` that tools would
+/// otherwise mistake for part of the template's own repository.
+const TEMPLATE_SUFFIX: &str = ".tmpl";
+
+/// Where a template's files live
+enum TemplateSource {
+    /// A built-in template compiled into murex
+    Embedded(&'static Dir<'static>),
+    /// A custom template directory, or a copy of one
+    Directory(PathBuf),
+}
+
+/// A template file before rendering
+struct SourceFile {
+    /// Path relative to the template root
+    path: PathBuf,
+    contents: Vec<u8>,
+    permissions: Option<fs::Permissions>,
+}
+
+impl TemplateSource {
+    fn manifest(&self) -> Result<Option<TemplateManifest>> {
+        match self {
+            TemplateSource::Embedded(dir) => match dir.get_file(dir.path().join(TEMPLATE_MANIFEST_FILE)) {
+                Some(file) => {
+                    let content = file.contents_utf8().unwrap_or_default();
+                    Ok(Some(TemplateManifest::parse(content, file.path())?))
+                }
+                None => Ok(None),
+            },
+            TemplateSource::Directory(root) => TemplateManifest::load(root),
+        }
+    }
+    
+    /// The files a project starts with, sorted by path
+    fn files(&self) -> Result<Vec<SourceFile>> {
+        let mut files = Vec::new();
+        
+        match self {
+            TemplateSource::Embedded(dir) => collect_embedded(dir, dir.path(), &mut files),
+            TemplateSource::Directory(root) => collect_directory(root, root, &template_ignore(root)?, &mut files)?,
+        }
+        
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+    
+    /// Render every file with `variables`
+    fn render(&self, variables: &Variables) -> Result<Vec<RenderedFile>> {
+        let filter = self.manifest()?
+            .map(|manifest| manifest.render)
+            .unwrap_or_default()
+            .compile()?;
+        
+        let mut seen = HashMap::new();
+        let mut files = Vec::new();
+        
+        for source in self.files()? {
+            let path = render_path(&source.path, variables)?;
+            if let Some(previous) = seen.insert(path.clone(), source.path.clone()) {
+                return Err(anyhow::anyhow!(
+                    "Template files '{}' and '{}' both become '{}'",
+                    previous.display(),
+                    source.path.display(),
+                    path.display()
+                ));
+            }
+            
+            // Binary files and files excluded from rendering are copied verbatim
+            let rendered = match as_text(&source.contents) {
+                Some(content) if filter.should_render(&path) => Some(
+                    render::render_str(content, variables)
+                        .map_err(|e| anyhow::anyhow!("{}: {}", source.path.display(), e))?,
+                ),
+                _ => None,
+            };
+            
+            files.push(RenderedFile {
+                contents: rendered.map(String::into_bytes).unwrap_or(source.contents),
+                permissions: source.permissions,
+                path,
+            });
+        }
+        
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+    
+    /// Write the template, manifest included, to `dest` without rendering it
+    fn export(&self, dest: &Path) -> Result<()> {
+        let files: Vec<RenderedFile> = self.files()?
+            .into_iter()
+            .map(|file| RenderedFile {
+                path: file.path,
+                contents: file.contents,
+                permissions: file.permissions,
+            })
+            .collect();
+        write_rendered(dest, &files)?;
+        
+        let manifest = match self {
+            TemplateSource::Embedded(dir) => dir
+                .get_file(dir.path().join(TEMPLATE_MANIFEST_FILE))
+                .map(|file| file.contents().to_vec()),
+            TemplateSource::Directory(root) => {
+                let path = root.join(TEMPLATE_MANIFEST_FILE);
+                if path.exists() { Some(fs::read(path)?) } else { None }
+            }
+        };
+        if let Some(manifest) = manifest {
+            fs::write(dest.join(TEMPLATE_MANIFEST_FILE), manifest)?;
+        }
+        
+        Ok(())
+    }
+}
+
+fn collect_embedded(dir: &'static Dir<'static>, root: &Path, files: &mut Vec<SourceFile>) {
+    for file in dir.files() {
+        let path = file.path().strip_prefix(root).unwrap_or(file.path());
+        if path == Path::new(TEMPLATE_MANIFEST_FILE) {
+            continue;
+        }
+        
+        files.push(SourceFile {
+            path: path.to_path_buf(),
+            contents: file.contents().to_vec(),
+            permissions: None,
+        });
+    }
+    
+    for subdir in dir.dirs() {
+        collect_embedded(subdir, root, files);
+    }
+}
+
+fn collect_directory(root: &Path, dir: &Path, ignore: &Gitignore, files: &mut Vec<SourceFile>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_dir = path.is_dir();
+        
+        if ignore.matched(&path, is_dir).is_ignore() {
+            continue;
+        }
+        
+        if is_dir {
+            collect_directory(root, &path, ignore, files)?;
+        } else {
+            files.push(SourceFile {
+                contents: fs::read(&path)?,
+                permissions: Some(fs::metadata(&path)?.permissions()),
+                path: path.strip_prefix(root).unwrap_or(&path).to_path_buf(),
+            });
+        }
+    }
+    
+    Ok(())
+}
+
+/// Check a name given to `template add` is usable as a single path component
+fn validate_template_name(name: &str) -> Result<()> {
+    if name.contains('/') {
+        return Err(anyhow::anyhow!("Template names can't contain '/'; it separates a pack's namespace"));
+    }
+    
+    if name.is_empty() || name.starts_with('.') || name.contains(INVALID_NAME_CHARS) || name.contains(char::is_control) {
+        return Err(anyhow::anyhow!("Invalid template name: '{}'", name));
+    }
+    
+    Ok(())
+}
+
+/// Whether `path` is a gzipped tarball, going by its name
+fn is_archive(path: &Path) -> bool {
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    path.is_file() && (name.ends_with(".tar.gz") || name.ends_with(".tgz"))
+}
+
+/// Default pack namespace: the directory or archive name without extensions
+fn pack_namespace(source: &Path) -> Result<String> {
+    let source = source.canonicalize()?;
+    let name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Can't name a pack after {}; pass --namespace", source.display()))?;
+    
+    let name = name
+        .strip_suffix(".tar.gz")
+        .or_else(|| name.strip_suffix(".tgz"))
+        .unwrap_or(name);
+    Ok(name.to_string())
+}
+
+fn unpack_archive(archive: &Path, dest: &Path) -> Result<()> {
+    let scratch = dest.with_extension("partial");
+    let _ = fs::remove_dir_all(&scratch);
+    fs::create_dir_all(&scratch)?;
+    
+    let decoder = GzDecoder::new(fs::File::open(archive)?);
+    if let Err(e) = tar::Archive::new(decoder).unpack(&scratch) {
+        let _ = fs::remove_dir_all(&scratch);
+        return Err(anyhow::anyhow!("Failed to unpack {}: {}", archive.display(), e));
+    }
+    
+    fs::rename(&scratch, dest)?;
+    Ok(())
+}
+
+/// Collect directories holding a template manifest, without looking inside templates
+fn find_template_dirs(dir: &Path, found: &mut Vec<PathBuf>) -> Result<()> {
+    if dir.join(TEMPLATE_MANIFEST_FILE).is_file() {
+        found.push(dir.to_path_buf());
+        return Ok(());
+    }
+    
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    entries.sort();
+    
+    for path in entries {
+        let hidden = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.'));
+        if path.is_dir() && !hidden {
+            find_template_dirs(&path, found)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// File at the root of a template listing paths not to copy, in gitignore syntax
+const IGNORE_FILE: &str = ".murexignore";
+
+/// Paths never copied from a template, before its own ignore file is applied
+const DEFAULT_IGNORES: &[&str] = &[
+    ".git/",
+    "target/",
+    "node_modules/",
+    "__pycache__/",
+    ".venv/",
+    ".DS_Store",
+    "*.swp",
+    "*.swo",
+    "*~",
+];
+
+/// Build the ignore rules for copying a template directory
+///
+/// The template's manifest and ignore file describe the template rather
+/// than the project, so they are always skipped. A `.murexignore` can
+/// re-include any other default with a `!pattern` line.
+fn template_ignore(root: &Path) -> Result<Gitignore> {
+    build_ignore(root, IGNORE_FILE, &[format!("/{}", TEMPLATE_MANIFEST_FILE), format!("/{}", IGNORE_FILE)])
+}
+
+/// Combine the default ignores, an ignore file under `root` and `always` patterns
+///
+/// `always` is added last so the ignore file can't re-include those paths.
+fn build_ignore(root: &Path, ignore_file: &str, always: &[String]) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    
+    for pattern in DEFAULT_IGNORES {
+        builder.add_line(None, pattern)?;
+    }
+    
+    let ignore_file = root.join(ignore_file);
+    if ignore_file.exists() {
+        if let Some(e) = builder.add(&ignore_file) {
+            return Err(anyhow::anyhow!("Failed to read {}: {}", ignore_file.display(), e));
+        }
+    }
+    
+    for pattern in always {
+        builder.add_line(None, pattern)?;
+    }
+    
+    Ok(builder.build()?)
+}
+
+/// Replace whole-word occurrences of `name` in `text` with `{{PROJECT_NAME}}`
+///
+/// A match must not be surrounded by letters or digits, so saving a project
+/// called `cli` doesn't turn `client` into `{{PROJECT_NAME}}ent`.
+fn templatize_name(text: &str, name: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut start = 0;
+    
+    while let Some(index) = text[start..].find(name).map(|index| start + index) {
+        let end = index + name.len();
+        let before = text[..index].chars().next_back();
+        let after = text[end..].chars().next();
+        let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+        
+        result.push_str(&text[start..index]);
+        if is_word(before) || is_word(after) {
+            result.push_str(name);
+        } else {
+            result.push_str("{{PROJECT_NAME}}");
+        }
+        start = end;
+    }
+    
+    result.push_str(&text[start..]);
+    result
+}
+
+/// Copy a project directory into a new template, replacing its name with a placeholder
+fn copy_as_template(src: &Path, dst: &Path, ignore: &Gitignore, project_name: &str) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let is_dir = src_path.is_dir();
+        
+        if ignore.matched(&src_path, is_dir).is_ignore() {
+            continue;
+        }
+        
+        let file_name = match entry.file_name().to_str() {
+            // Rendering strips one `.tmpl`, so files that already have one get another
+            Some(file_name) if !is_dir && file_name.ends_with(TEMPLATE_SUFFIX) => {
+                OsString::from(templatize_name(file_name, project_name) + TEMPLATE_SUFFIX)
+            }
+            Some(file_name) => OsString::from(templatize_name(file_name, project_name)),
+            None => entry.file_name(),
+        };
+        let dst_path = dst.join(file_name);
+        
+        if is_dir {
+            copy_as_template(&src_path, &dst_path, ignore, project_name)?;
+            continue;
+        }
+        
+        let bytes = fs::read(&src_path)?;
+        match as_text(&bytes) {
+            Some(content) => fs::write(&dst_path, templatize_name(&content.replace("{{", "\\{{"), project_name))?,
+            None => fs::write(&dst_path, &bytes)?,
+        }
+        fs::set_permissions(&dst_path, fs::metadata(&src_path)?.permissions())?;
+    }
+    
+    Ok(())
+}
+
+/// How much of a file is inspected when deciding whether it is text
+const TEXT_SNIFF_LEN: usize = 8000;
+
+/// Return the contents as a string if they look like text
+///
+/// Like git, a NUL byte near the start marks a file as binary; anything that
+/// isn't valid UTF-8 is treated as binary too so it is never rewritten.
+pub fn as_text(bytes: &[u8]) -> Option<&str> {
+    if bytes[..bytes.len().min(TEXT_SNIFF_LEN)].contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+/// Characters that can't appear in a file name on every platform we support
+const INVALID_NAME_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Render template variables in each component of a template-relative path
+///
+/// A trailing `.tmpl` is dropped from the file name.
+fn render_path(path: &Path, variables: &Variables) -> Result<PathBuf> {
+    let mut rendered = PathBuf::new();
+    for component in path.iter() {
+        rendered.push(render_name(component, path, variables)?);
+    }
+    
+    let stripped = rendered
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(TEMPLATE_SUFFIX))
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+    if let Some(name) = stripped {
+        rendered.set_file_name(name);
+    }
+    
+    Ok(rendered)
+}
+
+fn render_name(original: &OsStr, path: &Path, variables: &Variables) -> Result<OsString> {
+    let name = match original.to_str() {
+        Some(name) if name.contains("{{") => name,
+        _ => return Ok(original.to_os_string()),
+    };
+    
+    let rendered = render::render_str(name, variables)
+        .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+    
+    if rendered.contains("{{") {
+        return Err(anyhow::anyhow!("'{}' refers to an undefined variable", path.display()));
+    }
+    
+    if rendered.trim().is_empty() || rendered == "." || rendered == ".." {
+        return Err(anyhow::anyhow!("'{}' renders to an empty or reserved name '{}'", path.display(), rendered));
+    }
+    
+    if let Some(c) = rendered.chars().find(|c| INVALID_NAME_CHARS.contains(c) || c.is_control()) {
+        return Err(anyhow::anyhow!("'{}' renders to '{}', which contains the invalid character {:?}", path.display(), rendered, c));
+    }
+    
+    Ok(OsString::from(rendered))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A directory holding `files`
+    fn dir_with(files: &[(&str, &[u8])]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+    
+    fn read(dir: &Path, path: &str) -> String {
+        fs::read_to_string(dir.join(path)).unwrap()
+    }
+    
+    /// A manager whose only template is the directory template `name`
+    fn manager_with(name: &str, files: &[(&str, &[u8])]) -> (tempfile::TempDir, TemplateManager) {
+        let dir = dir_with(files);
+        
+        let mut manager = TemplateManager {
+            local_templates: HashMap::new(),
+            custom_templates: HashMap::new(),
+            pinned: HashMap::new(),
+        };
+        manager.local_templates.insert(name.to_string(), CustomTemplate {
+            name: name.to_string(),
+            path: dir.path().to_path_buf(),
+            description: None,
+            origin: None,
+            pack: None,
+        });
+        (dir, manager)
+    }
+    
+    #[test]
+    fn check_filters_files_by_their_rendered_path() {
+        let (_dir, manager) = manager_with("ci", &[
+            (TEMPLATE_MANIFEST_FILE, b"[build]\nlanguage = \"bash\"\n\n[render]\nexclude = [\"*.yaml\"]\n"),
+            ("ci.yaml.tmpl", b"token: {{ github_token }}\n"),
+        ]);
+        
+        let check = manager.check_template("ci", "demo").unwrap();
+        assert!(check.problems.is_empty(), "{:?}", check.problems);
+        
+        let files = check.files.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, Path::new("ci.yaml"));
+        assert_eq!(files[0].contents, b"token: {{ github_token }}\n");
+    }
+    
+    #[test]
+    fn templatizes_whole_words_only() {
+        assert_eq!(templatize_name("cli", "cli"), "{{PROJECT_NAME}}");
+        assert_eq!(
+            templatize_name("cargo build --bin cli && cp target/release/cli .", "cli"),
+            "cargo build --bin {{PROJECT_NAME}} && cp target/release/{{PROJECT_NAME}} ."
+        );
+        assert_eq!(templatize_name("client mycli cli2 clicli", "cli"), "client mycli cli2 clicli");
+        assert_eq!(templatize_name("client cli", "cli"), "client {{PROJECT_NAME}}");
+    }
+    
+    #[test]
+    fn templatizes_names_next_to_punctuation() {
+        assert_eq!(
+            templatize_name("cli-tool cli_x cli.rs (cli)", "cli"),
+            "{{PROJECT_NAME}}-tool {{PROJECT_NAME}}_x {{PROJECT_NAME}}.rs ({{PROJECT_NAME}})"
+        );
+    }
+    
+    /// Save `files` as project `cli` into a fresh template directory
+    fn save(files: &[(&str, &[u8])], always: &[&str]) -> (tempfile::TempDir, tempfile::TempDir) {
+        let project = dir_with(files);
+        let template = tempfile::tempdir().unwrap();
+        let always: Vec<String> = always.iter().map(|pattern| pattern.to_string()).collect();
+        let ignore = build_ignore(project.path(), ".gitignore", &always).unwrap();
+        
+        copy_as_template(project.path(), &template.path().join("t"), &ignore, "cli").unwrap();
+        (project, template)
+    }
+    
+    #[test]
+    fn escapes_existing_braces_in_saved_files() {
+        let (_project, template) = save(&[("README.md", b"cli prints {{ greeting }}\n")], &[]);
+        
+        assert_eq!(read(&template.path().join("t"), "README.md"), "{{PROJECT_NAME}} prints \\{{ greeting }}\n");
+    }
+    
+    #[test]
+    fn templatizes_file_and_directory_names() {
+        let (_project, template) = save(&[("cli/cli.sh", b"echo\n"), ("client.sh", b"echo\n")], &[]);
+        let dst = template.path().join("t");
+        
+        assert!(dst.join("{{PROJECT_NAME}}").join("{{PROJECT_NAME}}.sh").is_file());
+        assert!(dst.join("client.sh").is_file());
+    }
+    
+    #[test]
+    fn doubles_tmpl_suffixes_so_rendering_restores_them() {
+        let (_project, template) = save(&[("config.tmpl", b"x\n"), ("cli.tmpl", b"x\n")], &[]);
+        let dst = template.path().join("t");
+        
+        assert!(dst.join("config.tmpl.tmpl").is_file());
+        assert!(dst.join("{{PROJECT_NAME}}.tmpl.tmpl").is_file());
+    }
+    
+    #[test]
+    fn leaves_out_artifacts_and_ignored_files() {
+        let (_project, template) = save(
+            &[
+                ("main.sh", b"echo\n"),
+                ("cli", b"\0built"),
+                ("target/debug/cli", b"\0built"),
+                (".git/HEAD", b"ref\n"),
+                (".gitignore", b"*.log\n"),
+                ("debug.log", b"log\n"),
+                (PROJECT_MANIFEST_FILE, b"[project]\n"),
+            ],
+            &["/cli", "/murex.toml"],
+        );
+        let dst = template.path().join("t");
+        
+        let mut names: Vec<String> = fs::read_dir(&dst)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, [".gitignore", "main.sh"]);
+    }
+    
+    #[test]
+    fn copies_binary_files_verbatim() {
+        let (_project, template) = save(&[("logo.png", b"\0cli {{")], &[]);
+        
+        assert_eq!(fs::read(template.path().join("t").join("logo.png")).unwrap(), b"\0cli {{");
+    }
+    
+    #[test]
+    fn saved_templates_render_back_to_the_project() {
+        let files: &[(&str, &[u8])] = &[
+            ("cli.sh", b"#!/bin/sh\necho \"cli says {{ hi }}\"\n"),
+            ("config.tmpl", b"name = cli\n"),
+        ];
+        let (_project, template) = save(files, &[]);
+        
+        let mut variables = Variables::new();
+        variables.insert("PROJECT_NAME".to_string(), Value::String("cli".to_string()));
+        let rendered = TemplateSource::Directory(template.path().join("t")).render(&variables).unwrap();
+        
+        let rendered: Vec<(String, Vec<u8>)> = rendered
+            .into_iter()
+            .map(|file| (file.path.to_string_lossy().to_string(), file.contents))
+            .collect();
+        let expected: Vec<(String, Vec<u8>)> = files.iter().map(|(path, contents)| (path.to_string(), contents.to_vec())).collect();
+        assert_eq!(rendered, expected);
+    }
+    
+    fn variables(pairs: &[(&str, &str)]) -> Variables {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), Value::String(value.to_string())))
+            .collect()
+    }
+    
+    fn rendered_path(path: &str, pairs: &[(&str, &str)]) -> Result<PathBuf> {
+        render_path(Path::new(path), &variables(pairs))
+    }
+    
+    #[test]
+    fn renders_variables_in_every_path_component() {
+        let path = rendered_path("{{PROJECT_NAME}}/src/{{ module }}.rs", &[("PROJECT_NAME", "demo"), ("module", "core")]).unwrap();
+        
+        assert_eq!(path, Path::new("demo/src/core.rs"));
+    }
+    
+    #[test]
+    fn strips_one_tmpl_suffix_from_the_file_name() {
+        assert_eq!(rendered_path("Cargo.toml.tmpl", &[]).unwrap(), Path::new("Cargo.toml"));
+        assert_eq!(rendered_path("notes.tmpl.tmpl", &[]).unwrap(), Path::new("notes.tmpl"));
+        assert_eq!(rendered_path("docs.tmpl/guide.md", &[]).unwrap(), Path::new("docs.tmpl/guide.md"));
+        assert_eq!(rendered_path(".tmpl", &[]).unwrap(), Path::new(".tmpl"));
+    }
+    
+    #[test]
+    fn rejects_names_with_undefined_variables() {
+        let error = rendered_path("{{ missing }}.txt", &[]).unwrap_err();
+        
+        assert!(error.to_string().contains("undefined variable"), "{}", error);
+    }
+    
+    #[test]
+    fn rejects_names_that_render_empty_or_reserved() {
+        for value in ["", "  ", ".", ".."] {
+            let error = rendered_path("{{ dir }}/main.rs", &[("dir", value)]).unwrap_err();
+            assert!(error.to_string().contains("empty or reserved"), "{:?}: {}", value, error);
+        }
+    }
+    
+    #[test]
+    fn rejects_names_with_invalid_characters() {
+        for value in ["a/b", "a\\b", "c:", "what?", "tab\there"] {
+            let error = rendered_path("{{ name }}.txt", &[("name", value)]).unwrap_err();
+            assert!(error.to_string().contains("invalid character"), "{:?}: {}", value, error);
+        }
+    }
+    
+    #[test]
+    fn rejects_files_that_render_to_the_same_path() {
+        for files in [
+            [("{{PROJECT_NAME}}.txt", b"a" as &[u8]), ("demo.txt", b"b")],
+            [("notes.txt", b"a"), ("notes.txt.tmpl", b"b")],
+        ] {
+            let dir = dir_with(&files);
+            let error = TemplateSource::Directory(dir.path().to_path_buf())
+                .render(&variables(&[("PROJECT_NAME", "demo")]))
+                .unwrap_err();
+            assert!(error.to_string().contains("both become"), "{}", error);
+        }
+    }
+}
@@ -5,6 +5,8 @@ use std::env;
 use colored::*;
 
 use crate::config::Config;
+use crate::languages;
+use crate::manifest::PROJECT_MANIFEST_FILE;
 
 /// Check if a command is available in the system PATH
 pub fn command_exists(cmd: &str) -> bool {
@@ -15,7 +17,23 @@ pub fn command_exists(cmd: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Fail with a readable message if any of the given tools are missing from PATH
+pub fn ensure_tools<S: AsRef<str>>(tools: &[S]) -> Result<()> {
+    let missing: Vec<&str> = tools
+        .iter()
+        .map(|tool| tool.as_ref())
+        .filter(|tool| !command_exists(tool))
+        .collect();
+    
+    if !missing.is_empty() {
+        return Err(anyhow::anyhow!("Missing required tools: {}", missing.join(", ")));
+    }
+    
+    Ok(())
+}
+
 /// Get the current working directory as a string
+#[allow(dead_code)]
 pub fn current_dir_string() -> Result<String> {
     let current_dir = std::env::current_dir()?;
     Ok(current_dir.display().to_string())
@@ -27,12 +45,8 @@ pub fn is_valid_project_dir(path: &Path) -> bool {
         return false;
     }
     
-    // Check for common project indicators
-    path.join("Cargo.toml").exists() ||
-    path.join("package.json").exists() ||
-    path.join("go.mod").exists() ||
-    path.join("main.py").exists() ||
-    path.join("pyproject.toml").exists()
+    // Check for a murex manifest or any language's project markers
+    path.join(PROJECT_MANIFEST_FILE).exists() || languages::detect(path).is_some()
 }
 
 /// Create a symbolic link or copy file based on platform
@@ -54,6 +68,44 @@ pub fn create_link_or_copy(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Mark a script as executable (no-op on platforms without permission bits)
+pub fn make_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(path, perms)?;
+    }
+    
+    #[cfg(not(unix))]
+    let _ = path;
+    
+    Ok(())
+}
+
+/// Run a program with inherited stdio, exiting with its exit code
+///
+/// On Unix the current process is replaced outright so signals and exit
+/// codes pass through untouched.
+pub fn exec_program(program: &Path, args: &[String]) -> Result<()> {
+    let mut command = Command::new(program);
+    command.args(args);
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        Err(anyhow::anyhow!("Failed to run {}: {}", program.display(), err))
+    }
+    
+    #[cfg(not(unix))]
+    {
+        let status = command.status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
 /// Format file size in human readable format
 pub fn format_file_size(size: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -137,17 +189,11 @@ pub fn open_project_in_editor(project_dir: &Path, config: &Config) -> Result<()>
 
 /// Find the main file to open for a project
 fn find_main_file(project_dir: &Path) -> Result<std::path::PathBuf> {
-    // Try to find the main file based on common patterns
-    let candidates = vec![
-        "src/main.rs",
-        "main.py", 
-        "index.js",
-        "bun.js",
-        "main.go",
-        "main.sh",
-        "main.zsh",
-        "README.md",
-    ];
+    // Try to find the main file based on the known languages
+    let candidates = languages::all()
+        .iter()
+        .map(|language| language.main_file())
+        .chain(["README.md"]);
     
     for candidate in candidates {
         let file_path = project_dir.join(candidate);
@@ -0,0 +1,30 @@
+#!/bin/bash
+
+# {{PROJECT_NAME}}
+# A CLI utility created with Murex
+
+# Hello command
+hello() {
+    name=${1:-World}
+    echo "Hello, $name!"
+}
+
+# Main function
+main() {
+    case $1 in
+        hello)
+            hello $2
+            ;;
+        --help|-h)
+            echo "Usage: {{PROJECT_NAME}} [command] [options]"
+            echo "Commands:"
+            echo "  hello [name]  Say hello to someone"
+            echo "  --help        Show this help message"
+            ;;
+        *)
+            echo "Welcome to {{PROJECT_NAME}}! Use --help for more information."
+            ;;
+    esac
+}
+
+main "$@"
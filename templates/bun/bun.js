@@ -0,0 +1,24 @@
+#!/usr/bin/env bun
+
+import { Command } from 'bun';
+
+const program = new Command();
+
+program
+  .name('{{PROJECT_NAME}}')
+  .description('A CLI utility created with Murex')
+  .version('0.1.0');
+
+program
+  .command('hello')
+  .description('Say hello')
+  .option('-n, --name <n>', 'Name to greet', 'World')
+  .action((options) => {
+    console.log(`Hello, ${options.name}!`);
+  });
+
+if (process.argv.length === 2) {
+  console.log('Welcome to {{PROJECT_NAME}}! Use --help for more information.');
+} else {
+  program.parse();
+}
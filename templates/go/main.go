@@ -0,0 +1,38 @@
+package main
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+)
+
+var rootCmd = &cobra.Command{
+	Use:   "{{PROJECT_NAME}}",
+	Short: "A CLI utility created with Murex",
+	Long:  "A CLI utility created with Murex",
+	Run: func(cmd *cobra.Command, args []string) {
+		fmt.Println("Welcome to {{PROJECT_NAME}}! Use --help for more information.")
+	},
+}
+
+var helloCmd = &cobra.Command{
+	Use:   "hello",
+	Short: "Say hello",
+	Run: func(cmd *cobra.Command, args []string) {
+		name, _ := cmd.Flags().GetString("name")
+		fmt.Printf("Hello, %s!\n", name)
+	},
+}
+
+func init() {
+	helloCmd.Flags().StringP("name", "n", "World", "Name to greet")
+	rootCmd.AddCommand(helloCmd)
+}
+
+func main() {
+	if err := rootCmd.Execute(); err != nil {
+		fmt.Println(err)
+		os.Exit(1)
+	}
+}
@@ -0,0 +1,27 @@
+#!/usr/bin/env python3
+"""
+{{PROJECT_NAME}} - A CLI utility created with Murex
+"""
+
+import argparse
+import sys
+
+def main():
+    parser = argparse.ArgumentParser(description='A CLI utility created with Murex')
+    parser.add_argument('--version', action='version', version='%(prog)s 0.1.0')
+    
+    subparsers = parser.add_subparsers(dest='command', help='Available commands')
+    
+    # Hello command
+    hello_parser = subparsers.add_parser('hello', help='Say hello')
+    hello_parser.add_argument('-n', '--name', default='World', help='Name to greet')
+    
+    args = parser.parse_args()
+    
+    if args.command == 'hello':
+        print(f"Hello, {args.name}!")
+    else:
+        print(f"Welcome to {{PROJECT_NAME}}! Use --help for more information.")
+
+if __name__ == '__main__':
+    main()
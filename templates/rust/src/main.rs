@@ -0,0 +1,37 @@
+use clap::{Parser, Subcommand};
+use anyhow::Result;
+
+#[derive(Parser)]
+#[command(name = "{{PROJECT_NAME}}")]
+#[command(about = "A CLI utility created with Murex")]
+#[command(version = "0.1.0")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Say hello
+    Hello {
+        /// Name to greet
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    
+    match cli.command {
+        Some(Commands::Hello { name }) => {
+            let name = name.unwrap_or_else(|| "World".to_string());
+            println!("Hello, {}!", name);
+        }
+        None => {
+            println!("Welcome to {{PROJECT_NAME}}! Use --help for more information.");
+        }
+    }
+    
+    Ok(())
+}
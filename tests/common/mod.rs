@@ -0,0 +1,45 @@
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Isolated home and config directories for running murex
+pub struct Sandbox {
+    dir: TempDir,
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        Self {
+            dir: tempfile::tempdir().unwrap(),
+        }
+    }
+    
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+    
+    pub fn config_dir(&self) -> PathBuf {
+        self.path().join("config").join("murex")
+    }
+    
+    pub fn projects_dir(&self) -> PathBuf {
+        self.path().join("home").join(".murex")
+    }
+    
+    /// murex run from the sandbox root
+    pub fn murex(&self) -> Command {
+        self.murex_in(self.path())
+    }
+    
+    /// murex run from `current_dir`
+    pub fn murex_in(&self, current_dir: &Path) -> Command {
+        let mut cmd = Command::cargo_bin("murex").unwrap();
+        cmd.current_dir(current_dir)
+            .env("HOME", self.path().join("home"))
+            .env("XDG_CONFIG_HOME", self.path().join("config"))
+            .env("EDITOR", "true");
+        cmd
+    }
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::Sandbox;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Commit currently checked out for a git template
+fn pinned_commit(sandbox: &Sandbox, template: &str) -> String {
+    let output = sandbox.murex()
+        .args(["--format", "json", "template", "show", template])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    
+    let details: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    details["origin"]["commit"].as_str().unwrap().to_string()
+}
+
+/// Contents `init --dry-run` would give a project's hello.txt
+fn rendered_greeting(sandbox: &Sandbox, template: &str) -> String {
+    let output = sandbox.murex()
+        .args(["init", "demo", "--template", template, "--dry-run", "--show-contents"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("demo says"))
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// A working repository and a bare clone of it that templates are added from
+struct TemplateRepo {
+    work: PathBuf,
+    bare: PathBuf,
+}
+
+impl TemplateRepo {
+    /// Two commits on `main`, the first tagged `v1`
+    fn new(sandbox: &Sandbox, manifest: &str) -> Self {
+        let work = sandbox.path().join("work");
+        let bare = sandbox.path().join("template.git");
+        fs::create_dir_all(&work).unwrap();
+        
+        git(&work, &["init", "--quiet", "--initial-branch=main"]);
+        fs::write(work.join("murex-template.toml"), manifest).unwrap();
+        
+        let repo = Self { work, bare };
+        repo.commit("v1");
+        git(&repo.work, &["tag", "v1"]);
+        repo.commit("v2");
+        
+        git(sandbox.path(), &["clone", "--quiet", "--bare", "work", "template.git"]);
+        git(&repo.work, &["remote", "add", "origin", repo.bare.to_str().unwrap()]);
+        repo
+    }
+    
+    fn commit(&self, version: &str) -> String {
+        fs::write(self.work.join("hello.txt"), format!("{{{{PROJECT_NAME}}}} says {}\n", version)).unwrap();
+        git(&self.work, &["add", "-A"]);
+        git(&self.work, &["commit", "--quiet", "-m", version]);
+        git(&self.work, &["rev-parse", "HEAD"])
+    }
+    
+    fn commit_and_push(&self, version: &str) -> String {
+        let commit = self.commit(version);
+        git(&self.work, &["push", "--quiet", "origin", "main"]);
+        commit
+    }
+    
+    fn rev(&self, reference: &str) -> String {
+        git(&self.work, &["rev-parse", reference])
+    }
+    
+    fn url(&self) -> &str {
+        self.bare.to_str().unwrap()
+    }
+}
+
+const MANIFEST: &str = "description = \"git template\"\n\n[build]\nlanguage = \"bash\"\n";
+
+#[test]
+fn adds_a_template_from_a_bare_repository() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, MANIFEST);
+    
+    sandbox.murex()
+        .args(["template", "add", "tool", repo.url(), "--git"])
+        .assert()
+        .success();
+        
+    assert_eq!(pinned_commit(&sandbox, "tool"), repo.rev("main"));
+    assert_eq!(rendered_greeting(&sandbox, "tool"), "demo says v2");
+    assert!(sandbox.config_dir().join("repos").join("tool").join("hello.txt").exists());
+}
+
+#[test]
+fn pins_to_a_tag() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, MANIFEST);
+    
+    sandbox.murex()
+        .args(["template", "add", "tool", repo.url(), "--ref", "v1"])
+        .assert()
+        .success();
+        
+    assert_eq!(pinned_commit(&sandbox, "tool"), repo.rev("v1"));
+    assert_eq!(rendered_greeting(&sandbox, "tool"), "demo says v1");
+}
+
+#[test]
+fn pins_to_a_commit() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, MANIFEST);
+    let commit = repo.rev("v1");
+    
+    sandbox.murex()
+        .args(["template", "add", "tool", repo.url(), "--ref", &commit])
+        .assert()
+        .success();
+        
+    assert_eq!(pinned_commit(&sandbox, "tool"), commit);
+    
+    // A commit stays put even when the branch moves on
+    repo.commit_and_push("v3");
+    sandbox.murex().args(["template", "update", "tool"]).assert().success();
+    assert_eq!(pinned_commit(&sandbox, "tool"), commit);
+}
+
+#[test]
+fn update_follows_the_branch_and_can_switch_refs() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, MANIFEST);
+    
+    sandbox.murex()
+        .args(["template", "add", "tool", repo.url(), "--ref", "main"])
+        .assert()
+        .success();
+        
+    let v3 = repo.commit_and_push("v3");
+    sandbox.murex()
+        .args(["template", "update", "tool"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated template"));
+    assert_eq!(pinned_commit(&sandbox, "tool"), v3);
+    assert_eq!(rendered_greeting(&sandbox, "tool"), "demo says v3");
+    
+    sandbox.murex()
+        .args(["template", "update", "tool"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already at"));
+        
+    sandbox.murex()
+        .args(["template", "update", "tool", "--ref", "v1"])
+        .assert()
+        .success();
+    assert_eq!(pinned_commit(&sandbox, "tool"), repo.rev("v1"));
+}
+
+#[test]
+fn rolls_back_when_the_manifest_is_invalid() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, "description = [\n");
+    
+    sandbox.murex()
+        .args(["template", "add", "broken", repo.url(), "--git"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("murex-template.toml"));
+        
+    assert!(!sandbox.config_dir().join("repos").join("broken").exists());
+    sandbox.murex()
+        .args(["template", "show", "broken"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn rejects_names_that_escape_the_repos_directory() {
+    let sandbox = Sandbox::new();
+    let repo = TemplateRepo::new(&sandbox, MANIFEST);
+    
+    sandbox.murex()
+        .args(["template", "add", "../escaped", repo.url(), "--git"])
+        .assert()
+        .failure();
+        
+    assert!(!sandbox.config_dir().join("escaped").exists());
+}
+
+#[test]
+fn treats_sources_starting_with_a_dash_as_repositories() {
+    let sandbox = Sandbox::new();
+    let marker = sandbox.path().join("pwned");
+    let source = format!("--upload-pack=touch {}", marker.display());
+    
+    sandbox.murex()
+        .args(["template", "add", "tool", "--git", "--", &source])
+        .assert()
+        .failure();
+        
+    assert!(!marker.exists());
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use common::Sandbox;
+use predicates::prelude::*;
+
+#[test]
+fn refuses_to_guess_when_the_default_template_is_unknown() {
+    let sandbox = Sandbox::new();
+    
+    sandbox.murex()
+        .args(["config", "set", "default_template", "nope"])
+        .assert()
+        .success();
+        
+    sandbox.murex()
+        .args(["init", "demo", "--no-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("default_template 'nope'"));
+        
+    assert!(!sandbox.projects_dir().join("demo").exists());
+}
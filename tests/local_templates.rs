@@ -0,0 +1,62 @@
+mod common;
+
+use common::Sandbox;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+/// A tree with a project-local template `name` whose notes.txt reads `notes`
+fn tree(sandbox: &Sandbox, tree: &str, name: &str, notes: &str) -> PathBuf {
+    let root = sandbox.path().join(tree);
+    let template = root.join(".murex-templates").join(name);
+    fs::create_dir_all(&template).unwrap();
+    fs::write(template.join("murex-template.toml"), "[build]\nlanguage = \"bash\"\n").unwrap();
+    fs::write(template.join("notes.txt"), notes).unwrap();
+    root
+}
+
+#[test]
+fn syncs_from_the_local_template_a_project_was_created_from() {
+    let sandbox = Sandbox::new();
+    let origin = tree(&sandbox, "origin", "tool", "v1\n");
+    let other = tree(&sandbox, "other", "tool", "unrelated\n");
+    
+    sandbox.murex_in(&origin)
+        .args(["init", "demo", "--template", "tool", "--no-input"])
+        .assert()
+        .success();
+        
+    let manifest = fs::read_to_string(sandbox.projects_dir().join("demo").join("murex.toml")).unwrap();
+    assert!(manifest.contains("template_scope = \"project\""), "{}", manifest);
+    
+    fs::write(origin.join(".murex-templates").join("tool").join("notes.txt"), "v2\n").unwrap();
+    
+    // Neither the sandbox root nor a tree with its own `tool` changes which template is used
+    for dir in [sandbox.path(), other.as_path()] {
+        sandbox.murex_in(dir)
+            .args(["template", "sync", "demo", "--no-input"])
+            .assert()
+            .success();
+    }
+    
+    assert_eq!(fs::read_to_string(sandbox.projects_dir().join("demo").join("notes.txt")).unwrap(), "v2\n");
+}
+
+#[test]
+fn fails_to_sync_when_the_local_template_is_gone() {
+    let sandbox = Sandbox::new();
+    let origin = tree(&sandbox, "origin", "tool", "v1\n");
+    
+    sandbox.murex_in(&origin)
+        .args(["init", "demo", "--template", "tool", "--no-input"])
+        .assert()
+        .success();
+        
+    fs::remove_dir_all(origin.join(".murex-templates")).unwrap();
+    
+    sandbox.murex()
+        .args(["template", "sync", "demo", "--no-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no longer exists"));
+}
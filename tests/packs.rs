@@ -0,0 +1,116 @@
+mod common;
+
+use common::Sandbox;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const MANIFEST: &str = "[build]\nlanguage = \"bash\"\n";
+
+/// A pack source tree under `<sandbox>/src/<root>` with one template per entry
+fn pack_tree(sandbox: &Sandbox, root: &str, templates: &[(&str, &str)]) -> PathBuf {
+    let dir = sandbox.path().join("src").join(root);
+    for (name, manifest) in templates {
+        let template = dir.join(name);
+        fs::create_dir_all(&template).unwrap();
+        fs::write(template.join("murex-template.toml"), manifest).unwrap();
+        fs::write(template.join("hello.txt"), format!("{} from {{{{PROJECT_NAME}}}}\n", name)).unwrap();
+    }
+    dir
+}
+
+/// Archive `entry` of `dir` into `<sandbox>/<name>`
+fn tar(sandbox: &Sandbox, name: &str, dir: &Path, entry: &str) -> PathBuf {
+    let archive = sandbox.path().join(name);
+    let output = process::Command::new("tar")
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(dir)
+        .arg(entry)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "tar: {}", String::from_utf8_lossy(&output.stderr));
+    archive
+}
+
+#[test]
+fn unwraps_the_single_top_level_directory_of_an_archive() {
+    let sandbox = Sandbox::new();
+    pack_tree(&sandbox, "tools", &[("cli", MANIFEST), ("web", MANIFEST)]);
+    let archive = tar(&sandbox, "tools.tar.gz", &sandbox.path().join("src"), "tools");
+    
+    sandbox.murex()
+        .args(["template", "add-pack"])
+        .arg(&archive)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tools/cli").and(predicate::str::contains("tools/tools").not()));
+        
+    sandbox.murex()
+        .args(["init", "demo", "--template", "tools/web", "--dry-run", "--show-contents"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("web from demo"));
+        
+    assert!(sandbox.config_dir().join("packs").join("tools").is_dir());
+}
+
+#[test]
+fn uses_the_archive_root_when_it_holds_several_entries() {
+    let sandbox = Sandbox::new();
+    let dir = pack_tree(&sandbox, "tools", &[("cli", MANIFEST), ("web", MANIFEST)]);
+    let archive = tar(&sandbox, "flat.tar.gz", &dir, ".");
+    
+    sandbox.murex()
+        .args(["template", "add-pack"])
+        .arg(&archive)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flat/cli").and(predicate::str::contains("flat/web")));
+}
+
+#[test]
+fn removes_the_unpacked_archive_when_adding_fails() {
+    let sandbox = Sandbox::new();
+    pack_tree(&sandbox, "broken", &[("cli", MANIFEST), ("bad", "description = [\n")]);
+    let archive = tar(&sandbox, "broken.tar.gz", &sandbox.path().join("src"), "broken");
+    
+    sandbox.murex()
+        .args(["template", "add-pack"])
+        .arg(&archive)
+        .assert()
+        .failure();
+        
+    assert!(!sandbox.config_dir().join("packs").join("broken").exists());
+    sandbox.murex()
+        .args(["template", "show", "broken/cli"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn remove_pack_deletes_the_unpacked_archive() {
+    let sandbox = Sandbox::new();
+    pack_tree(&sandbox, "tools", &[("cli", MANIFEST)]);
+    let archive = tar(&sandbox, "tools.tar.gz", &sandbox.path().join("src"), "tools");
+    
+    sandbox.murex()
+        .args(["template", "add-pack", "--namespace", "kit"])
+        .arg(&archive)
+        .assert()
+        .success();
+    assert!(sandbox.config_dir().join("packs").join("kit").is_dir());
+    
+    sandbox.murex()
+        .args(["template", "remove-pack", "kit"])
+        .assert()
+        .success();
+        
+    assert!(!sandbox.config_dir().join("packs").join("kit").exists());
+    sandbox.murex()
+        .args(["template", "show", "kit/cli"])
+        .assert()
+        .failure();
+}
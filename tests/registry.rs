@@ -0,0 +1,53 @@
+mod common;
+
+use common::Sandbox;
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn lists_projects_after_the_registry_is_lost() {
+    let sandbox = Sandbox::new();
+    
+    sandbox.murex()
+        .args(["init", "demo", "--template", "bash", "--no-input"])
+        .assert()
+        .success();
+        
+    fs::remove_file(sandbox.config_dir().join("projects.json")).unwrap();
+    
+    sandbox.murex()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("demo"));
+        
+    sandbox.murex()
+        .args(["build", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("demo"));
+}
+
+#[test]
+fn lists_the_remaining_projects_when_a_manifest_is_broken() {
+    let sandbox = Sandbox::new();
+    
+    for name in ["demo", "other"] {
+        sandbox.murex()
+            .args(["init", name, "--template", "bash", "--no-input"])
+            .assert()
+            .success();
+    }
+    
+    let projects_dir = sandbox.projects_dir();
+    fs::write(projects_dir.join("other").join("murex.toml"), "garbage [[").unwrap();
+    fs::create_dir_all(projects_dir.join("stray")).unwrap();
+    fs::write(projects_dir.join("stray").join("murex.toml"), "garbage [[").unwrap();
+    
+    sandbox.murex()
+        .args(["--format", "plain", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("demo").and(predicate::str::contains("other")))
+        .stderr(predicate::str::contains("Skipping"));
+}